@@ -0,0 +1,282 @@
+use std::{collections::HashMap, env, rc::Rc, str::FromStr, thread, time::Duration};
+
+use anchor_client::{
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::AccountMeta,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature},
+        system_program,
+    },
+    Client, Cluster, Program,
+};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, ID as ASSOCIATED_TOKEN_PROGRAM_ID};
+use anyhow::{anyhow, bail, Result};
+use mpl_core::{Asset, Collection, ID as CORE_PROGRAM_ID};
+use mpl_core_auction::{accounts, instruction, AssetAuction, AuctionKind, CollectionAuction, Config, WhitelistKind, ID as PROGRAM_ID};
+
+
+const USAGE: &str = "usage: keeper [--url <url>] [--keypair <path>] [--config <pubkey>] [--interval <seconds>] [--once]";
+
+
+// keeper settings, read from the command line
+struct Args {
+    url: String,
+    keypair: String,
+    config: Option<Pubkey>, // only settle auctions of this config
+    interval: u64,
+    once: bool,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        url: "localnet".to_string(),
+        keypair: format!("{}/.config/solana/id.json", env::var("HOME").unwrap_or_default()),
+        config: None,
+        interval: 10,
+        once: false,
+    };
+
+    let mut argv = env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        let mut value = || argv.next().ok_or_else(|| anyhow!("missing value for {arg}\n{USAGE}"));
+        match arg.as_str() {
+            "--url" => args.url = value()?,
+            "--keypair" => args.keypair = value()?,
+            "--config" => args.config = Some(Pubkey::from_str(&value()?)?),
+            "--interval" => args.interval = value()?.parse()?,
+            "--once" => args.once = true,
+            _ => bail!("unknown argument {arg}\n{USAGE}"),
+        }
+    }
+
+    Ok(args)
+}
+
+
+// permissionless instruction settling an ended asset auction
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action {
+    Complete,
+    Expire,
+    CompleteSealed,
+}
+
+fn next_action(auction: &AssetAuction, unix_timestamp: i64) -> Option<Action> {
+    if unix_timestamp < auction.end_timestamp {
+        return None;
+    }
+
+    match auction.kind {
+        AuctionKind::English if auction.first_bid_timestamp != 0 => Some(Action::Complete),
+        AuctionKind::English if auction.is_scheduled() => Some(Action::Expire),
+        AuctionKind::Sealed { settled: false, .. } => Some(Action::CompleteSealed),
+        _ => None,
+    }
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+}
+
+
+// find ended asset auctions and settle them, one transaction per auction
+fn crank(program: &Program<Rc<Keypair>>, config_filter: Option<Pubkey>) -> Result<()> {
+    let rpc = program.rpc();
+    let unix_timestamp = rpc.get_block_time(rpc.get_slot()?)?;
+
+    // program accounts are fetched with getProgramAccounts, filtered by discriminator. legacy layout
+    // configs share the config discriminator, accounts that do not decode are skipped until migrated
+    let configs: Vec<(Pubkey, Config)> = match config_filter {
+        Some(config) => vec![(config, program.account(config)?)],
+        None => program.accounts_lazy(vec![])?.filter_map(Result::ok).collect(),
+    };
+    let collection_auctions: Vec<(Pubkey, CollectionAuction)> = program.accounts_lazy(vec![])?.filter_map(Result::ok).collect();
+    let asset_auctions: Vec<(Pubkey, AssetAuction)> = program.accounts_lazy(vec![])?.filter_map(Result::ok).collect();
+
+    // collection auction -> config, asset auctions do not store their config
+    let mut collection_configs = HashMap::new();
+    for (config, _) in &configs {
+        for (collection_auction, account) in &collection_auctions {
+            let seeds: [&[u8]; 4] = [b"collection", config.as_ref(), account.collection.as_ref(), &[account.bump]];
+            if Pubkey::create_program_address(&seeds, &PROGRAM_ID).ok() == Some(*collection_auction) {
+                collection_configs.insert(*collection_auction, (*config, account));
+            }
+        }
+    }
+
+    for (asset_auction, auction) in &asset_auctions {
+        let Some(action) = next_action(auction, unix_timestamp) else {
+            continue;
+        };
+
+        // asset auction pda is derived from its collection auction
+        let collection = collection_configs.iter().find(|(collection_auction, (_, account))| {
+            let seeds: [&[u8]; 3] = [collection_auction.as_ref(), auction.core_asset.as_ref(), &[auction.bump]];
+            account.collection == auction.collection && Pubkey::create_program_address(&seeds, &PROGRAM_ID).ok() == Some(*asset_auction)
+        });
+        let Some((collection_auction, (config, collection_account))) = collection else {
+            continue;
+        };
+
+        match settle(program, action, *config, *collection_auction, collection_account, *asset_auction, auction) {
+            Ok(signature) => println!("{action:?} {asset_auction}: {signature}"),
+            Err(error) => eprintln!("{action:?} {asset_auction} failed: {error:#}"),
+        }
+    }
+
+    Ok(())
+}
+
+fn settle(
+    program: &Program<Rc<Keypair>>,
+    action: Action,
+    config: Pubkey,
+    collection_auction: Pubkey,
+    collection_account: &CollectionAuction,
+    asset_auction: Pubkey,
+    auction: &AssetAuction,
+) -> Result<Signature> {
+    let payer = program.payer();
+    let vault = pda(&[b"vault", config.as_ref()]);
+    let tresuary = pda(&[b"tresuary", config.as_ref()]);
+    let collection_stats = pda(&[b"stats", collection_auction.as_ref()]);
+    let bid_history = pda(&[b"bid_history", asset_auction.as_ref()]);
+    let event_authority = pda(&[b"__event_authority"]);
+
+    // core collection of the whitelist, standalone assets of an update authority whitelist have none
+    let collection = match collection_account.kind {
+        WhitelistKind::Collection => Some(collection_account.collection),
+        WhitelistKind::UpdateAuthority => None,
+    };
+
+    // quote mint collections settle in token accounts, the token program owns the mint
+    let quote_mint = collection_account.quote_mint;
+    let token_program = match quote_mint {
+        Some(mint) => Some(program.rpc().get_account(&mint)?.owner),
+        None => None,
+    };
+    let token_account = |authority: &Pubkey| {
+        quote_mint
+            .zip(token_program)
+            .map(|(mint, token_program)| get_associated_token_address_with_program_id(authority, &mint, &token_program))
+    };
+
+    let request = match action {
+        Action::Expire => program
+            .request()
+            .accounts(accounts::ExpireAssetAuction {
+                payer,
+                owner: auction.owner,
+                collection,
+                asset: auction.core_asset,
+                config,
+                collection_auction,
+                asset_auction,
+                bid_history,
+                system_program: system_program::ID,
+                core_program: CORE_PROGRAM_ID,
+                event_authority,
+                program: PROGRAM_ID,
+            })
+            .args(instruction::ExpireAssetAuction {}),
+        Action::Complete => program
+            .request()
+            .accounts(accounts::CompleteAssetAuction {
+                payer,
+                owner: auction.owner,
+                buyer: auction.buyer,
+                collection,
+                asset: auction.core_asset,
+                bid_referrer: auction.bid_referrer,
+                listing_referrer: auction.listing_referrer,
+                config,
+                tresuary,
+                vault,
+                collection_auction,
+                collection_stats,
+                asset_auction,
+                bid_history,
+                quote_mint,
+                owner_token_account: token_account(&auction.owner),
+                tresuary_token_account: token_account(&tresuary),
+                payer_token_account: token_account(&payer),
+                vault_token_account: token_account(&vault),
+                system_program: system_program::ID,
+                token_program,
+                associated_token_program: quote_mint.map(|_| ASSOCIATED_TOKEN_PROGRAM_ID),
+                core_program: CORE_PROGRAM_ID,
+                event_authority,
+                program: PROGRAM_ID,
+            })
+            .accounts(creator_accounts(program, auction, collection, |creator| token_account(creator).unwrap_or(*creator))?)
+            .args(instruction::CompleteAssetAuction {}),
+        Action::CompleteSealed => program
+            .request()
+            .accounts(accounts::CompleteSealedAssetAuction {
+                payer,
+                owner: auction.owner,
+                buyer: auction.buyer,
+                funder: auction.funder,
+                collection: auction.collection,
+                asset: auction.core_asset,
+                config,
+                tresuary,
+                vault,
+                collection_auction,
+                collection_stats,
+                asset_auction,
+                winner_bid: (auction.buyer != auction.owner)
+                    .then(|| pda(&[b"sealed_bid", asset_auction.as_ref(), auction.buyer.as_ref()])),
+                system_program: system_program::ID,
+                core_program: CORE_PROGRAM_ID,
+                event_authority,
+                program: PROGRAM_ID,
+            })
+            .accounts(creator_accounts(program, auction, collection, |creator| *creator)?)
+            .args(instruction::CompleteSealedAssetAuction {}),
+    };
+
+    Ok(request.send()?)
+}
+
+// creator payout accounts in the royalties plugin order, asset plugin first then collection plugin
+fn creator_accounts(program: &Program<Rc<Keypair>>, auction: &AssetAuction, collection: Option<Pubkey>, payout: impl Fn(&Pubkey) -> Pubkey) -> Result<Vec<AccountMeta>> {
+    let rpc = program.rpc();
+    let asset = Asset::from_bytes(&rpc.get_account_data(&auction.core_asset)?)?;
+    let royalties = match (asset.plugin_list.royalties, collection) {
+        (Some(plugin), _) => Some(plugin.royalties),
+        (None, Some(collection)) => Collection::from_bytes(&rpc.get_account_data(&collection)?)?
+            .plugin_list
+            .royalties
+            .map(|plugin| plugin.royalties),
+        (None, None) => None,
+    };
+
+    Ok(royalties.map_or_else(Vec::new, |royalties| {
+        royalties
+            .creators
+            .iter()
+            .map(|creator| AccountMeta::new(payout(&creator.address), false))
+            .collect()
+    }))
+}
+
+
+fn main() -> Result<()> {
+    let args = parse_args()?;
+    let payer = read_keypair_file(&args.keypair).map_err(|error| anyhow!("failed to read keypair {}: {error}", args.keypair))?;
+    let client = Client::new_with_options(Cluster::from_str(&args.url)?, Rc::new(payer), CommitmentConfig::confirmed());
+    let program = client.program(PROGRAM_ID)?;
+
+    loop {
+        let result = crank(&program, args.config);
+        if args.once {
+            return result;
+        }
+        if let Err(error) = result {
+            eprintln!("crank failed: {error:#}");
+        }
+        thread::sleep(Duration::from_secs(args.interval));
+    }
+}
@@ -7,6 +7,8 @@ pub enum AuctionErrors {
     InvalidAdmin,
     #[msg("Invalid min and max duration!!")]
     InvalidMinMaxDuration,
+    #[msg("Invalid auction extension params!!")]
+    InvalidExtension,
     #[msg("Duration is shorter than the minimum allowed duration!!")]
     DurationTooShort,
     #[msg("Duration is longer than the maximum allowed duration!!")]
@@ -27,6 +29,118 @@ pub enum AuctionErrors {
     AuctionRunning,
     #[msg("New bid is lower or equal than the current bid!!")]
     InvalidBid,
+    #[msg("Opening bid is lower than the auction min bid!!")]
+    BidBelowMinimum,
+    #[msg("New bid does not beat the current bid by the minimum increment!!")]
+    BidIncrementTooLow,
     #[msg("The owner of the asset can not bid!!")]
     OwnerBid, 
+    #[msg("Instruction not supported by this auction kind!!")]
+    InvalidAuctionKind,
+    #[msg("Dutch start price must be higher than the floor price!!")]
+    InvalidDutchPrices,
+    #[msg("Invalid dutch auction price decay!!")]
+    InvalidPriceDecay,
+    #[msg("Current price is higher than the max price!!")]
+    PriceAboveMax,
+    #[msg("Reveal phase duration must be positive!!")]
+    InvalidRevealDuration,
+    #[msg("Penalty can not be higher than 10000 bps!!")]
+    InvalidPenalty,
+    #[msg("Commit phase is over, impossible to commit bid!!")]
+    CommitPhaseOver,
+    #[msg("Reveal phase has not started, impossible to reveal bid!!")]
+    RevealPhaseNotStarted,
+    #[msg("Reveal phase is over, impossible to reveal bid!!")]
+    RevealPhaseOver,
+    #[msg("Reveal phase is still running, impossible to complete!!")]
+    RevealPhaseRunning,
+    #[msg("Sealed bid already revealed!!")]
+    AlreadyRevealed,
+    #[msg("Revealed bid does not match the commitment!!")]
+    InvalidReveal,
+    #[msg("Revealed bid is higher than the deposit!!")]
+    DepositTooLow,
+    #[msg("Auction already settled!!")]
+    AuctionSettled,
+    #[msg("Auction is not settled, impossible to refund!!")]
+    AuctionNotSettled,
+    #[msg("Winner sealed bid account is missing!!")]
+    MissingWinnerBid,
+    #[msg("Buy now price must be higher than the min bid and the cutoff!!")]
+    InvalidBuyNowPrice,
+    #[msg("Buy now is not available for this auction!!")]
+    BuyNowUnavailable,
+    #[msg("Quote mint does not match the collection auction!!")]
+    InvalidQuoteMint,
+    #[msg("Quote mint token accounts are missing!!")]
+    MissingTokenAccounts,
+    #[msg("Quote mint collections only support english auctions without buy now!!")]
+    QuoteMintNotSupported,
+    #[msg("Creator accounts do not match the royalties plugin!!")]
+    InvalidCreators,
+    #[msg("Tresuary balance too low, it has to stay rent exempt!!")]
+    InsufficientTreasury,
+    #[msg("Math overflow!!")]
+    MathOverflow,
+    #[msg("Collection auction is paused!!")]
+    CollectionPaused,
+    #[msg("Collection auction still has open auctions!!")]
+    CollectionHasOpenAuctions,
+    #[msg("Only the admin or the pauser can pause the program!!")]
+    InvalidPauser,
+    #[msg("Program is paused!!")]
+    ProgramPaused,
+    #[msg("Program is not paused, emergency refunds are disabled!!")]
+    ProgramNotPaused,
+    #[msg("Auction schedule must end after it starts and in the future!!")]
+    InvalidSchedule,
+    #[msg("Auction has not opened yet!!")]
+    AuctionNotOpen,
+    #[msg("Auction is not scheduled, it can not expire!!")]
+    AuctionNotScheduled,
+    #[msg("Keeper reward share can not be higher than the fee!!")]
+    InvalidKeeperReward,
+    #[msg("Bid refund account does not match the outbid bid!!")]
+    InvalidBidRefund,
+    #[msg("Only the funder or the bidder can withdraw a refund!!")]
+    InvalidRefundAuthority,
+    #[msg("English auctions need their bid history account!!")]
+    MissingBidHistory,
+    #[msg("Offers need a positive amount and quantity and an expiry in the future!!")]
+    InvalidOffer,
+    #[msg("Offer has expired!!")]
+    OfferExpired,
+    #[msg("Trait offers need 1 to 4 traits with keys and values up to 32 bytes!!")]
+    InvalidTraits,
+    #[msg("Asset attributes do not match the offer traits!!")]
+    TraitMismatch,
+    #[msg("Attributes plugin must be managed by the update authority!!")]
+    AttributesNotUpdateAuthority,
+    #[msg("Lot assets must be 1 to 8 distinct assets of the collection, passed in lot order!!")]
+    InvalidLotAssets,
+    #[msg("Lot assets must share the same royalties!!")]
+    LotRoyaltiesMismatch,
+    #[msg("Only the seller or the update authority can set the allowlist!!")]
+    InvalidAllowlistAuthority,
+    #[msg("Bidder is not in the auction allowlist!!")]
+    NotAllowlisted,
+    #[msg("Invalid bid gate!!")]
+    InvalidGate,
+    #[msg("Gate asset or token account is missing!!")]
+    MissingGateAccount,
+    #[msg("Bidder does not meet the collection bid gate!!")]
+    GateNotMet,
+    #[msg("Fee is above the max fee bps!!")]
+    InvalidFee,
+    #[msg("Settlement payouts do not add up to the price!!")]
+    InvalidSettlement,
+    #[msg("Config account is not in the legacy layout!!")]
+    ConfigAlreadyMigrated,
+    #[msg("Invalid referrer fee!!")]
+    InvalidReferrerFee,
+    #[msg("Invalid referrer!!")]
+    InvalidReferrer,
+    #[msg("Collection account does not match the whitelist!!")]
+    InvalidCollection,
 }
\ No newline at end of file
@@ -0,0 +1,353 @@
+use anchor_lang::prelude::*;
+
+use crate::{AssetAuction, AuctionKind, Settlement, Trait};
+
+
+// events are emitted with emit_cpi, asset auction events carry the collection and asset keys, lot events the lot key
+#[event]
+pub struct ConfigInitialized {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub seed: u32,
+    pub fee_bps: u16,
+    pub min_duration_minutes: u32,
+    pub max_duration_minutes: u32,
+}
+
+#[event]
+pub struct CollectionWhitelisted {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub collection_auction: Pubkey,
+    pub quote_mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct AuctionCreated {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub asset_auction: Pubkey,
+    pub owner: Pubkey,
+    pub kind: AuctionKind,
+    pub min_bid_lamports: u64,
+    pub buy_now_lamports: Option<u64>,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64, // 0 when the clock starts at the first bid
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub asset_auction: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub asset_auction: Pubkey,
+    pub bidder: Pubkey,
+    pub funder: Pubkey,
+    pub bid_lamports: u64,
+    pub previous_bidder: Option<Pubkey>, // none for the opening bid
+    pub previous_bid_lamports: u64,
+    pub refund_lamports: u64, // pending refund of the previous bidder after this bid
+    pub referrer: Option<Pubkey>,
+    pub end_timestamp: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionCompleted {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub asset_auction: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub quote_mint: Option<Pubkey>, // prices are in the quote mint when set
+    pub price_lamports: u64,
+    pub fee_lamports: u64, // tresuary fee including the keeper reward and referrer shares
+    pub keeper_lamports: u64,
+    pub referrer_lamports: u64,
+    pub royalty_lamports: u64,
+    pub seller_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SealedBidCommitted {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub asset_auction: Pubkey,
+    pub bidder: Pubkey,
+    pub funder: Pubkey,
+    pub deposit_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SealedBidRevealed {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub asset_auction: Pubkey,
+    pub bidder: Pubkey,
+    pub bid_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SealedBidRefunded {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub asset_auction: Pubkey,
+    pub bidder: Pubkey,
+    pub funder: Pubkey,
+    pub refund_lamports: u64,
+    pub penalty_lamports: u64, // unrevealed bids pay it to the owner
+    pub timestamp: i64,
+}
+
+// refund accounts only store the auction account
+#[event]
+pub struct RefundWithdrawn {
+    pub config: Pubkey,
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub funder: Pubkey,
+    pub quote_mint: Option<Pubkey>, // refund is in the quote mint when set
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferMade {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub lamports: u64,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+pub struct OfferCancelled {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferAccepted {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub offer: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price_lamports: u64,
+    pub fee_lamports: u64,
+    pub royalty_lamports: u64,
+    pub seller_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionOfferMade {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub collection_offer: Pubkey,
+    pub bidder: Pubkey,
+    pub lamports: u64,
+    pub quantity: u32,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+pub struct CollectionOfferCancelled {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub collection_offer: Pubkey,
+    pub bidder: Pubkey,
+    pub refund_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionOfferFilled {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub collection_offer: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price_lamports: u64,
+    pub fee_lamports: u64,
+    pub royalty_lamports: u64,
+    pub seller_lamports: u64,
+    pub remaining: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TraitOfferMade {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub trait_offer: Pubkey,
+    pub bidder: Pubkey,
+    pub lamports: u64,
+    pub traits: Vec<Trait>,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+pub struct TraitOfferCancelled {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub trait_offer: Pubkey,
+    pub bidder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TraitOfferAccepted {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub trait_offer: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price_lamports: u64,
+    pub fee_lamports: u64,
+    pub royalty_lamports: u64,
+    pub seller_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LotCreated {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub lot_auction: Pubkey,
+    pub owner: Pubkey,
+    pub assets: Vec<Pubkey>,
+    pub min_bid_lamports: u64,
+}
+
+#[event]
+pub struct LotCancelled {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub lot_auction: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LotBidPlaced {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub lot_auction: Pubkey,
+    pub bidder: Pubkey,
+    pub funder: Pubkey,
+    pub bid_lamports: u64,
+    pub previous_bidder: Option<Pubkey>, // none for the opening bid
+    pub previous_bid_lamports: u64,
+    pub refund_lamports: u64,
+    pub end_timestamp: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LotCompleted {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub lot_auction: Pubkey,
+    pub assets: Vec<Pubkey>,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price_lamports: u64,
+    pub fee_lamports: u64, // tresuary fee including the keeper reward
+    pub keeper_lamports: u64,
+    pub royalty_lamports: u64,
+    pub seller_lamports: u64,
+    pub timestamp: i64,
+}
+
+// sealed auctions settle with a sale, or hand the asset back when no bid reached the reserve
+pub enum SealedSettlement {
+    Sold(AuctionCompleted),
+    Unsold(AuctionCancelled),
+}
+
+
+impl AuctionCreated {
+    pub fn new(config: Pubkey, asset_auction: &Account<AssetAuction>) -> Self {
+        AuctionCreated {
+            config,
+            collection: asset_auction.collection,
+            asset: asset_auction.core_asset,
+            asset_auction: asset_auction.key(),
+            owner: asset_auction.owner,
+            kind: asset_auction.kind,
+            min_bid_lamports: asset_auction.min_bid_lamports,
+            buy_now_lamports: asset_auction.buy_now_lamports,
+            start_timestamp: asset_auction.start_timestamp,
+            end_timestamp: asset_auction.end_timestamp,
+        }
+    }
+}
+
+impl AuctionCancelled {
+    pub fn new(config: Pubkey, asset_auction: &Account<AssetAuction>) -> Result<Self> {
+        Ok(AuctionCancelled {
+            config,
+            collection: asset_auction.collection,
+            asset: asset_auction.core_asset,
+            asset_auction: asset_auction.key(),
+            owner: asset_auction.owner,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
+
+impl AuctionCompleted {
+    pub fn new(
+        config: Pubkey,
+        asset_auction: &Account<AssetAuction>,
+        buyer: Pubkey,
+        quote_mint: Option<Pubkey>,
+        price_lamports: u64,
+        settlement: &Settlement,
+    ) -> Result<Self> {
+        Ok(AuctionCompleted {
+            config,
+            collection: asset_auction.collection,
+            asset: asset_auction.core_asset,
+            asset_auction: asset_auction.key(),
+            seller: asset_auction.owner,
+            buyer,
+            quote_mint,
+            price_lamports,
+            fee_lamports: settlement.fee_lamports(),
+            keeper_lamports: settlement.keeper_lamports,
+            referrer_lamports: settlement.bid_referrer_lamports + settlement.listing_referrer_lamports,
+            royalty_lamports: settlement.royalty_lamports,
+            seller_lamports: settlement.owner_lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
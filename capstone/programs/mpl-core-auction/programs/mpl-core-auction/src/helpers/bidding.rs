@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuctionErrors, CollectionAuction, Config};
+
+
+// outbid rules shared by english asset auctions and lots, the bid must beat the current one by the
+// min increment before the end, and a bid close to the end extends the auction (anti-sniping).
+// returns the new end timestamp and extension count
+pub fn outbid(
+    config: &Config,
+    collection_auction: &CollectionAuction,
+    current_bid_lamports: u64,
+    lamports: u64,
+    end_timestamp: i64,
+    extensions: u16,
+    unix_timestamp: i64,
+) -> Result<(i64, u16)> {
+    // check that auction is not over
+    require!(unix_timestamp < end_timestamp, AuctionErrors::AuctionEnded);
+
+    // check min bid increment
+    let min_increment = config.min_bid_increment.increment(current_bid_lamports);
+    require!(lamports - current_bid_lamports >= min_increment, AuctionErrors::BidIncrementTooLow);
+
+    // anti-sniping, extend auction when bidding close to the end
+    let extension = collection_auction.extension.unwrap_or(config.extension);
+    let in_window = end_timestamp - unix_timestamp <= extension.window_minutes as i64 * 60;
+    if in_window && extensions < extension.max_extensions {
+        return Ok((end_timestamp + extension.extension_minutes as i64 * 60, extensions + 1));
+    }
+
+    Ok((end_timestamp, extensions))
+}
@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_plugin,
+    instructions::{
+        AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder, RemovePluginV1CpiBuilder,
+        RevokePluginAuthorityV1CpiBuilder, TransferV1CpiBuilder, UpdatePluginV1CpiBuilder,
+    },
+    types::{FreezeDelegate, Plugin, PluginAuthority, PluginType, TransferDelegate},
+};
+
+use crate::AuctionErrors;
+
+
+// Freeze and transfer delegate custody of an mpl-core asset, delegated to an auction pda
+pub struct AssetCustody<'a, 'info> {
+    pub core_program: &'a AccountInfo<'info>,
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> AssetCustody<'a, 'info> {
+    // freeze the asset and delegate freeze and transfer authority to the auction pda
+    pub fn lock(&self, owner: &AccountInfo<'info>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        // check if freeze delegate plugin exists
+        match fetch_plugin::<BaseAssetV1, FreezeDelegate>(self.asset, PluginType::FreezeDelegate) {
+            Ok((plugin_authority, freeze_delegate, _)) => {
+                // check if asset is frozen or has an active freeze delegate
+                require!(freeze_delegate.frozen == false, AuctionErrors::FrozenAsset);
+                require!(plugin_authority == PluginAuthority::Owner, AuctionErrors::FreezeDelegateNotOwner);
+
+                // update freeze delegate authority
+                ApprovePluginAuthorityV1CpiBuilder::new(self.core_program)
+                    .asset(self.asset)
+                    .collection(self.collection)
+                    .payer(self.payer)
+                    .authority(Some(owner))
+                    .system_program(self.system_program)
+                    .plugin_type(PluginType::FreezeDelegate)
+                    .new_authority(PluginAuthority::Address { address: self.authority.key() })
+                    .invoke()?;
+
+                // freeze with auction pda seeds signature
+                UpdatePluginV1CpiBuilder::new(self.core_program)
+                    .asset(self.asset)
+                    .collection(self.collection)
+                    .payer(self.payer)
+                    .authority(Some(self.authority))
+                    .system_program(self.system_program)
+                    .plugin(Plugin::FreezeDelegate( FreezeDelegate { frozen: true } ))
+                    .invoke_signed(signer_seeds)?;
+            }
+            Err(_) => {
+                // Freeze the asset
+                AddPluginV1CpiBuilder::new(self.core_program)
+                    .asset(self.asset)
+                    .collection(self.collection)
+                    .payer(self.payer)
+                    .authority(Some(owner))
+                    .system_program(self.system_program)
+                    .plugin(Plugin::FreezeDelegate( FreezeDelegate { frozen: true } ))
+                    .init_authority(PluginAuthority::Address { address: self.authority.key() })
+                    .invoke()?;
+            }
+        };
+
+        // check if transfer delegate plugin exists
+        match fetch_plugin::<BaseAssetV1, TransferDelegate>(self.asset, PluginType::TransferDelegate) {
+            Ok((plugin_authority, _, _)) => {
+                // check if asset has active transfer delegate
+                require!(plugin_authority == PluginAuthority::Owner, AuctionErrors::TransferDelegateNotOwner);
+
+                // update transfer delegate authority
+                ApprovePluginAuthorityV1CpiBuilder::new(self.core_program)
+                    .asset(self.asset)
+                    .collection(self.collection)
+                    .payer(self.payer)
+                    .authority(Some(owner))
+                    .system_program(self.system_program)
+                    .plugin_type(PluginType::TransferDelegate)
+                    .new_authority(PluginAuthority::Address { address: self.authority.key() })
+                    .invoke()?;
+            }
+            Err(_) => {
+                // Add transfer delegate
+                AddPluginV1CpiBuilder::new(self.core_program)
+                    .asset(self.asset)
+                    .collection(self.collection)
+                    .payer(self.payer)
+                    .authority(Some(owner))
+                    .system_program(self.system_program)
+                    .plugin(Plugin::TransferDelegate( TransferDelegate { } ))
+                    .init_authority(PluginAuthority::Address { address: self.authority.key() })
+                    .invoke()?;
+            }
+        };
+
+        Ok(())
+    }
+
+    // thaw the asset and remove the auction plugins, asset stays with the owner
+    pub fn unlock(&self, owner: &AccountInfo<'info>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        // thaw asset
+        self.thaw(signer_seeds)?;
+
+        // remove freeze delegate plugin
+        RemovePluginV1CpiBuilder::new(self.core_program)
+            .asset(self.asset)
+            .collection(self.collection)
+            .payer(self.payer)
+            .authority(Some(owner))
+            .system_program(self.system_program)
+            .plugin_type(PluginType::FreezeDelegate)
+            .invoke()?;
+
+        // revoke transfer delegate
+        RevokePluginAuthorityV1CpiBuilder::new(self.core_program)
+            .asset(self.asset)
+            .collection(self.collection)
+            .payer(self.payer)
+            .authority(Some(self.authority))
+            .system_program(self.system_program)
+            .plugin_type(PluginType::TransferDelegate)
+            .invoke_signed(signer_seeds)?;
+
+        // remove transfer delegate plugin
+        RemovePluginV1CpiBuilder::new(self.core_program)
+            .asset(self.asset)
+            .collection(self.collection)
+            .payer(self.payer)
+            .authority(Some(owner))
+            .system_program(self.system_program)
+            .plugin_type(PluginType::TransferDelegate)
+            .invoke()?;
+
+        Ok(())
+    }
+
+    // thaw the asset and transfer it to the new owner
+    pub fn release(&self, new_owner: &AccountInfo<'info>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        // thaw asset
+        self.thaw(signer_seeds)?;
+
+        // transfer asset to new owner
+        TransferV1CpiBuilder::new(self.core_program)
+            .asset(self.asset)
+            .collection(self.collection)
+            .payer(self.payer)
+            .authority(Some(self.authority))
+            .new_owner(new_owner)
+            .system_program(Some(self.system_program))
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    // thaw the asset and hand the delegates back to the owner, no owner signature needed
+    pub fn return_to_owner(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        // thaw asset
+        self.thaw(signer_seeds)?;
+
+        // revoke freeze and transfer delegates
+        for plugin_type in [PluginType::FreezeDelegate, PluginType::TransferDelegate] {
+            RevokePluginAuthorityV1CpiBuilder::new(self.core_program)
+                .asset(self.asset)
+                .collection(self.collection)
+                .payer(self.payer)
+                .authority(Some(self.authority))
+                .system_program(self.system_program)
+                .plugin_type(plugin_type)
+                .invoke_signed(signer_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    fn thaw(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        UpdatePluginV1CpiBuilder::new(self.core_program)
+            .asset(self.asset)
+            .collection(self.collection)
+            .payer(self.payer)
+            .authority(Some(self.authority))
+            .system_program(self.system_program)
+            .plugin(Plugin::FreezeDelegate( FreezeDelegate { frozen: false } ))
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
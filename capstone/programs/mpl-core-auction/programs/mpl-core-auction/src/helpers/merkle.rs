@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+
+// allowlist leaf of a bidder key
+pub fn allowlist_leaf(bidder: &Pubkey) -> [u8; 32] {
+    hashv(&[bidder.as_ref()]).to_bytes()
+}
+
+// verify a merkle proof, pairs are hashed in sorted order so the proof needs no side flags
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == root
+}
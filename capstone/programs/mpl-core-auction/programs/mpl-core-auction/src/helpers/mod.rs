@@ -0,0 +1,15 @@
+pub mod vault;
+pub mod token_vault;
+pub mod settlement;
+pub mod custody;
+pub mod royalties;
+pub mod bidding;
+pub mod merkle;
+
+pub use vault::*;
+pub use token_vault::*;
+pub use settlement::*;
+pub use custody::*;
+pub use royalties::*;
+pub use bidding::*;
+pub use merkle::*;
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    fetch_plugin,
+    types::{PluginType, Royalties},
+};
+
+use crate::AuctionErrors;
+
+
+// creator royalties of an asset, creators are paid out of the seller proceeds
+pub struct CreatorRoyalties<'a, 'info> {
+    pub basis_points: u16,
+    pub creators: Vec<(&'a AccountInfo<'info>, u8)>, // creator payout account and percentage
+}
+
+impl<'a, 'info> CreatorRoyalties<'a, 'info> {
+    // read the asset royalties plugin, falling back to the collection one of collection assets.
+    // creator accounts must follow the plugin creators order, they are the creator
+    // token accounts when a quote mint and token program are given
+    pub fn fetch(
+        asset: &AccountInfo<'info>,
+        collection: Option<&AccountInfo<'info>>,
+        creator_accounts: &'a [AccountInfo<'info>],
+        quote_mint: Option<(Pubkey, Pubkey)>,
+    ) -> Result<Self> {
+        let Some(royalties) = plugin_royalties(asset, collection) else {
+            require!(creator_accounts.is_empty(), AuctionErrors::InvalidCreators);
+            return Ok(CreatorRoyalties { basis_points: 0, creators: Vec::new() });
+        };
+
+        // validate creator accounts
+        require!(creator_accounts.len() == royalties.creators.len(), AuctionErrors::InvalidCreators);
+        let mut creators = Vec::with_capacity(creator_accounts.len());
+        for (creator, creator_account) in royalties.creators.iter().zip(creator_accounts.iter()) {
+            let payout_address = match quote_mint {
+                None => creator.address,
+                Some((mint, token_program)) => get_associated_token_address_with_program_id(&creator.address, &mint, &token_program),
+            };
+            require_keys_eq!(creator_account.key(), payout_address, AuctionErrors::InvalidCreators);
+            require!(creator_account.is_writable, AuctionErrors::InvalidCreators);
+            creators.push((creator_account, creator.percentage));
+        }
+
+        Ok(CreatorRoyalties { basis_points: royalties.basis_points, creators })
+    }
+
+    // split a royalty amount between creators, the last creator gets the rounding remainder
+    pub fn payouts(&self, royalty: u64) -> Vec<(&'a AccountInfo<'info>, u64)> {
+        let mut remaining = royalty;
+        let mut payouts = Vec::with_capacity(self.creators.len());
+        for (i, (creator_account, percentage)) in self.creators.iter().enumerate() {
+            let amount = match i == self.creators.len() - 1 {
+                true => remaining,
+                false => (royalty as u128 * *percentage as u128 / 100) as u64,
+            };
+            remaining -= amount;
+            payouts.push((*creator_account, amount));
+        }
+        payouts
+    }
+}
+
+
+// lots settle every asset with one royalties plugin, so all of them must resolve to the same one
+pub fn same_royalties(asset_accounts: &[AccountInfo], collection: Option<&AccountInfo>) -> bool {
+    let mut royalties = asset_accounts.iter().map(|asset| plugin_royalties(asset, collection));
+    match royalties.next() {
+        Some(first) => royalties.all(|royalties| royalties == first),
+        None => true,
+    }
+}
+
+// royalties plugin of an asset, falling back to the collection one of collection assets
+fn plugin_royalties(asset: &AccountInfo, collection: Option<&AccountInfo>) -> Option<Royalties> {
+    fetch_plugin::<BaseAssetV1, Royalties>(asset, PluginType::Royalties)
+        .or_else(|error| match collection {
+            Some(collection) => fetch_plugin::<BaseCollectionV1, Royalties>(collection, PluginType::Royalties),
+            None => Err(error),
+        })
+        .map(|(_, royalties, _)| royalties)
+        .ok()
+}
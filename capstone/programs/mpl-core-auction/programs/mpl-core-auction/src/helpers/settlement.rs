@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuctionErrors, Config, CreatorRoyalties, KeeperReward, ReferrerFee, transfer_from_vault};
+
+
+// split of the sale price between tresuary, keeper, referrers, creators and owner
+pub struct Settlement {
+    pub tresuary_lamports: u64,
+    pub keeper_lamports: u64,
+    pub bid_referrer_lamports: u64,
+    pub listing_referrer_lamports: u64,
+    pub royalty_lamports: u64,
+    pub owner_lamports: u64,
+}
+
+impl Settlement {
+    pub fn new(price_lamports: u64, fee_bps: u16, royalty_bps: u16, keeper_reward: Option<KeeperReward>, referrer_fee: Option<ReferrerFee>) -> Result<Self> {
+        // fee is rounded up, the owner gets the exact remainder of the price
+        let fee_lamports = mul_bps_ceil(price_lamports, fee_bps)?;
+        let owner_gross_lamports = price_lamports.checked_sub(fee_lamports).ok_or(AuctionErrors::MathOverflow)?;
+
+        // keeper reward is paid out of the fee
+        let keeper_lamports = keeper_reward.map_or(0, |keeper_reward| keeper_reward.reward(fee_lamports));
+        let mut tresuary_lamports = fee_lamports.checked_sub(keeper_lamports).ok_or(AuctionErrors::MathOverflow)?;
+
+        // referrer shares are paid out of what is left of the fee
+        let referrer_fee = referrer_fee.unwrap_or_default();
+        let bid_referrer_lamports = mul_bps_floor(fee_lamports, referrer_fee.bid_bps)?.min(tresuary_lamports);
+        tresuary_lamports -= bid_referrer_lamports;
+        let listing_referrer_lamports = mul_bps_floor(fee_lamports, referrer_fee.listing_bps)?.min(tresuary_lamports);
+        tresuary_lamports -= listing_referrer_lamports;
+
+        // royalties are paid out of the owner proceeds
+        let royalty_lamports = mul_bps_floor(price_lamports, royalty_bps)?.min(owner_gross_lamports);
+        let owner_lamports = owner_gross_lamports.checked_sub(royalty_lamports).ok_or(AuctionErrors::MathOverflow)?;
+
+        let settlement = Settlement {
+            tresuary_lamports,
+            keeper_lamports,
+            bid_referrer_lamports,
+            listing_referrer_lamports,
+            royalty_lamports,
+            owner_lamports,
+        };
+        require!(settlement.total()? == price_lamports, AuctionErrors::InvalidSettlement);
+        Ok(settlement)
+    }
+
+    // protocol fee including the keeper reward and referrer shares
+    pub fn fee_lamports(&self) -> u64 {
+        self.tresuary_lamports + self.keeper_lamports + self.bid_referrer_lamports + self.listing_referrer_lamports
+    }
+
+    // every payout adds up to the sale price
+    pub fn total(&self) -> Result<u64> {
+        [self.keeper_lamports, self.bid_referrer_lamports, self.listing_referrer_lamports, self.royalty_lamports, self.owner_lamports]
+            .iter()
+            .try_fold(self.tresuary_lamports, |total, lamports| total.checked_add(*lamports))
+            .ok_or(error!(AuctionErrors::MathOverflow))
+    }
+}
+
+// share of an amount in basis points, rounded up
+pub fn mul_bps_ceil(amount: u64, bps: u16) -> Result<u64> {
+    let product = (amount as u128).checked_mul(bps as u128).ok_or(AuctionErrors::MathOverflow)?;
+    let share = product.checked_add(9_999).ok_or(AuctionErrors::MathOverflow)? / 10_000;
+    u64::try_from(share).map_err(|_| error!(AuctionErrors::MathOverflow))
+}
+
+// share of an amount in basis points, rounded down
+pub fn mul_bps_floor(amount: u64, bps: u16) -> Result<u64> {
+    let product = (amount as u128).checked_mul(bps as u128).ok_or(AuctionErrors::MathOverflow)?;
+    u64::try_from(product / 10_000).map_err(|_| error!(AuctionErrors::MathOverflow))
+}
+
+// referrers of a sale, the bid referrer of the winning bid and the listing referrer of the auction
+#[derive(Default)]
+pub struct Referrers<'a, 'info> {
+    pub bid: Option<&'a AccountInfo<'info>>,
+    pub listing: Option<&'a AccountInfo<'info>>,
+}
+
+impl Referrers<'_, '_> {
+    // config shares of the referrers present in the sale
+    pub fn fee(&self, referrer_fee: Option<ReferrerFee>) -> Option<ReferrerFee> {
+        referrer_fee.map(|referrer_fee| ReferrerFee {
+            bid_bps: if self.bid.is_some() { referrer_fee.bid_bps } else { 0 },
+            listing_bps: if self.listing.is_some() { referrer_fee.listing_bps } else { 0 },
+        })
+    }
+}
+
+// pay the sale price held in the vault to the tresuary, the keeper, the creators and the owner.
+// the keeper is the permissionless caller settling the auction, None when the buyer settles
+pub fn settle_sale<'info>(
+    config: &Account<'info, Config>,
+    vault: &AccountInfo<'info>,
+    tresuary: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    keeper: Option<&AccountInfo<'info>>,
+    royalties: &CreatorRoyalties<'_, 'info>,
+    price_lamports: u64,
+) -> Result<Settlement> {
+    settle_referred_sale(config, vault, tresuary, owner, keeper, &Referrers::default(), royalties, price_lamports)
+}
+
+// settle_sale with a share of the fee paid to the referrers of the sale
+#[allow(clippy::too_many_arguments)]
+pub fn settle_referred_sale<'info>(
+    config: &Account<'info, Config>,
+    vault: &AccountInfo<'info>,
+    tresuary: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    keeper: Option<&AccountInfo<'info>>,
+    referrers: &Referrers<'_, 'info>,
+    royalties: &CreatorRoyalties<'_, 'info>,
+    price_lamports: u64,
+) -> Result<Settlement> {
+    let keeper_reward = keeper.and(config.keeper_reward);
+    let mut settlement = Settlement::new(price_lamports, config.fee_bps, royalties.basis_points, keeper_reward, referrers.fee(config.referrer_fee))?;
+
+    // a share leaving an empty referrer or creator account below rent exemption would fail the whole
+    // sale, and the winning bidder picks the bid referrer, so dust shares go to the tresuary instead
+    let rent = Rent::get()?;
+    if referrers.bid.is_some_and(|referrer| is_dust(referrer, settlement.bid_referrer_lamports, &rent)) {
+        settlement.tresuary_lamports += std::mem::take(&mut settlement.bid_referrer_lamports);
+    }
+    if referrers.listing.is_some_and(|referrer| is_dust(referrer, settlement.listing_referrer_lamports, &rent)) {
+        settlement.tresuary_lamports += std::mem::take(&mut settlement.listing_referrer_lamports);
+    }
+    let mut royalty_payouts = royalties.payouts(settlement.royalty_lamports);
+    for (creator, lamports) in royalty_payouts.iter_mut() {
+        if is_dust(creator, *lamports, &rent) {
+            settlement.royalty_lamports -= *lamports;
+            settlement.tresuary_lamports += std::mem::take(lamports);
+        }
+    }
+
+    // transfer fee to tresuary
+    transfer_from_vault(config, vault, tresuary, settlement.tresuary_lamports)?;
+
+    // transfer reward to keeper
+    if let Some(keeper) = keeper {
+        if settlement.keeper_lamports > 0 {
+            transfer_from_vault(config, vault, keeper, settlement.keeper_lamports)?;
+        }
+    }
+
+    // transfer referrer shares
+    for (referrer, lamports) in [
+        (referrers.bid, settlement.bid_referrer_lamports),
+        (referrers.listing, settlement.listing_referrer_lamports),
+    ] {
+        if let Some(referrer) = referrer {
+            if lamports > 0 {
+                transfer_from_vault(config, vault, referrer, lamports)?;
+            }
+        }
+    }
+
+    // transfer royalties to creators
+    for (creator, lamports) in royalty_payouts {
+        if lamports > 0 {
+            transfer_from_vault(config, vault, creator, lamports)?;
+        }
+    }
+
+    // transfer funds to owner
+    transfer_from_vault(config, vault, owner, settlement.owner_lamports)?;
+
+    Ok(settlement)
+}
+
+// a payout leaving the account with lamports below rent exemption fails the transfer
+fn is_dust(to: &AccountInfo, lamports: u64, rent: &Rent) -> bool {
+    lamports > 0 && !rent.is_exempt(to.lamports().saturating_add(lamports), to.data_len())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+
+use crate::{Config, CreatorRoyalties, Settlement};
+
+
+// vault pda token account for a collection quote mint
+pub struct TokenVault<'a, 'info> {
+    pub config: &'a Account<'info, Config>,
+    pub vault: &'a SystemAccount<'info>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> TokenVault<'a, 'info> {
+    // transfer tokens from a signer token account into the vault
+    pub fn deposit(&self, from: &InterfaceAccount<'info, TokenAccount>, authority: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let token_program_transfer_accounts = TransferChecked {
+            from: from.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault_token_account.to_account_info(),
+            authority: authority.clone(),
+        };
+
+        let cpi_context = CpiContext::new(
+            self.token_program.to_account_info(),
+            token_program_transfer_accounts,
+        );
+
+        transfer_checked(cpi_context, amount, self.mint.decimals)
+    }
+
+    // transfer tokens out of the vault with the vault pda seeds signature
+    pub fn withdraw(&self, to: &InterfaceAccount<'info, TokenAccount>, amount: u64) -> Result<()> {
+        self.withdraw_to(&to.to_account_info(), amount)
+    }
+
+    // same as withdraw for unchecked token accounts, the token program validates them
+    pub fn withdraw_to(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let token_program_transfer_accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: to.clone(),
+            authority: self.vault.to_account_info(),
+        };
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"vault",
+            self.config.to_account_info().key.as_ref(),
+            &[self.config.vault_bump],
+        ]];
+
+        let cpi_context = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            token_program_transfer_accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(cpi_context, amount, self.mint.decimals)
+    }
+
+    // pay the sale price held in the vault to the tresuary, keeper, creators and owner token accounts
+    pub fn settle_sale(
+        &self,
+        tresuary: &InterfaceAccount<'info, TokenAccount>,
+        owner: &InterfaceAccount<'info, TokenAccount>,
+        keeper: Option<&InterfaceAccount<'info, TokenAccount>>,
+        royalties: &CreatorRoyalties<'_, 'info>,
+        price: u64,
+    ) -> Result<Settlement> {
+        let keeper_reward = keeper.and(self.config.keeper_reward);
+        let settlement = Settlement::new(price, self.config.fee_bps, royalties.basis_points, keeper_reward, None)?;
+
+        // transfer fee to tresuary
+        self.withdraw(tresuary, settlement.tresuary_lamports)?;
+
+        // transfer reward to keeper token account
+        if let Some(keeper) = keeper {
+            if settlement.keeper_lamports > 0 {
+                self.withdraw(keeper, settlement.keeper_lamports)?;
+            }
+        }
+
+        // transfer royalties to creator token accounts
+        for (creator, amount) in royalties.payouts(settlement.royalty_lamports) {
+            if amount > 0 {
+                self.withdraw_to(creator, amount)?;
+            }
+        }
+
+        // transfer funds to owner
+        self.withdraw(owner, settlement.owner_lamports)?;
+
+        Ok(settlement)
+    }
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{system_instruction::transfer, program::{invoke_signed, invoke}};
+
+use crate::Config;
+
+
+// transfer lamports from a signer into the vault
+pub fn transfer_to_vault<'info>(from: &AccountInfo<'info>, vault: &AccountInfo<'info>, lamports: u64) -> Result<()> {
+    let ix = transfer(
+        from.key,
+        vault.key,
+        lamports,
+    );
+
+    invoke(
+        &ix,
+        &[
+            from.clone(),
+            vault.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+// transfer lamports out of the vault with the vault pda seeds signature
+pub fn transfer_from_vault<'info>(config: &Account<'info, Config>, vault: &AccountInfo<'info>, to: &AccountInfo<'info>, lamports: u64) -> Result<()> {
+    let ix = transfer(
+        vault.key,
+        to.key,
+        lamports,
+    );
+
+    let signer_seeds: [&[&[u8]]; 1] = [&[
+        b"vault",
+        config.to_account_info().key.as_ref(),
+        &[config.vault_bump],
+    ]];
+
+    invoke_signed(
+        &ix,
+        &[
+            vault.clone(),
+            to.clone(),
+        ],
+        &signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+// transfer lamports out of the tresuary with the tresuary pda seeds signature
+pub fn transfer_from_tresuary<'info>(config: &Account<'info, Config>, tresuary: &AccountInfo<'info>, to: &AccountInfo<'info>, lamports: u64) -> Result<()> {
+    let ix = transfer(
+        tresuary.key,
+        to.key,
+        lamports,
+    );
+
+    let signer_seeds: [&[&[u8]]; 1] = [&[
+        b"tresuary",
+        config.to_account_info().key.as_ref(),
+        &[config.tresuary_bump],
+    ]];
+
+    invoke_signed(
+        &ix,
+        &[
+            tresuary.clone(),
+            to.clone(),
+        ],
+        &signer_seeds,
+    )?;
+
+    Ok(())
+}
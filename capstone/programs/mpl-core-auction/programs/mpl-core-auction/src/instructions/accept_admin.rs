@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    // EXTERNAL ACCOUNTS
+    pub pending_admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        require!(self.config.pending_admin == Some(self.pending_admin.key()), AuctionErrors::InvalidAdmin);
+
+        self.config.admin = self.pending_admin.key();
+        self.config.pending_admin = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    instructions::TransferV1CpiBuilder,
+    types::UpdateAuthority,
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{CollectionAuction, CollectionStats, Config, Offer, AuctionErrors, CreatorRoyalties, OfferAccepted, settle_sale};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = offer.bidder,
+    )]
+    pub bidder: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()),
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"tresuary", config.key().as_ref()],
+        bump = config.tresuary_bump,
+    )]
+    pub tresuary: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = config,
+        has_one = asset,
+        seeds = [b"offer", config.key().as_ref(), asset.key().as_ref(), bidder.key().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+
+impl<'info> AcceptOffer<'info> {
+    pub fn accept_offer(&mut self, creator_accounts: &[AccountInfo<'info>]) -> Result<OfferAccepted> {
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        require!(unix_timestamp < self.offer.expiry_timestamp, AuctionErrors::OfferExpired);
+
+        // creator royalties, creator accounts are passed as remaining accounts
+        let royalties = CreatorRoyalties::fetch(
+            &self.asset.to_account_info(),
+            Some(&self.collection.to_account_info()),
+            creator_accounts,
+            None,
+        )?;
+
+        // pay fee to tresuary, royalties to creators and the escrowed offer to owner
+        let price_lamports = self.offer.lamports;
+        let settlement = settle_sale(
+            &self.config,
+            &self.vault.to_account_info(),
+            &self.tresuary.to_account_info(),
+            &self.owner.to_account_info(),
+            None,
+            &royalties,
+            price_lamports,
+        )?;
+
+        // record sale in the collection stats
+        self.collection_stats.record(self.asset.key(), price_lamports, unix_timestamp);
+
+        // transfer asset to bidder, listed assets are frozen and can not be transferred
+        TransferV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.owner.to_account_info())
+            .authority(Some(&self.owner.to_account_info()))
+            .new_owner(&self.bidder.to_account_info())
+            .system_program(Some(&self.system_program.to_account_info()))
+            .invoke()?;
+
+        Ok(OfferAccepted {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            asset: self.asset.key(),
+            offer: self.offer.key(),
+            seller: self.owner.key(),
+            buyer: self.bidder.key(),
+            price_lamports,
+            fee_lamports: settlement.fee_lamports(),
+            royalty_lamports: settlement.royalty_lamports,
+            seller_lamports: settlement.owner_lamports,
+            timestamp: unix_timestamp,
+        })
+    }
+}
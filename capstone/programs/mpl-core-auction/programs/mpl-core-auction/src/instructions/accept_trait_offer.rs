@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    fetch_plugin,
+    instructions::TransferV1CpiBuilder,
+    types::{Attributes, PluginAuthority, PluginType, UpdateAuthority},
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{CollectionAuction, CollectionStats, Config, TraitOffer, AuctionErrors, CreatorRoyalties, TraitOfferAccepted, settle_sale};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptTraitOffer<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = trait_offer.bidder,
+    )]
+    pub bidder: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()),
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"tresuary", config.key().as_ref()],
+        bump = config.tresuary_bump,
+    )]
+    pub tresuary: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = config,
+        has_one = collection_auction,
+        seeds = [b"trait_offer", collection_auction.key().as_ref(), bidder.key().as_ref(), trait_offer.seed.to_le_bytes().as_ref()],
+        bump = trait_offer.bump,
+    )]
+    pub trait_offer: Box<Account<'info, TraitOffer>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+
+impl<'info> AcceptTraitOffer<'info> {
+    pub fn accept_trait_offer(&mut self, creator_accounts: &[AccountInfo<'info>]) -> Result<TraitOfferAccepted> {
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        require!(self.owner.key() != self.trait_offer.bidder, AuctionErrors::OwnerBid);
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        require!(unix_timestamp < self.trait_offer.expiry_timestamp, AuctionErrors::OfferExpired);
+
+        // asset attributes must match every trait before any funds move, owner managed
+        // attributes could be rewritten by the seller so only the update authority is trusted
+        let (plugin_authority, attributes, _) = fetch_plugin::<BaseAssetV1, Attributes>(&self.asset.to_account_info(), PluginType::Attributes)
+            .map_err(|_| AuctionErrors::TraitMismatch)?;
+        require!(plugin_authority == PluginAuthority::UpdateAuthority, AuctionErrors::AttributesNotUpdateAuthority);
+        require!(self.trait_offer.matches(&attributes.attribute_list), AuctionErrors::TraitMismatch);
+
+        // creator royalties, creator accounts are passed as remaining accounts
+        let royalties = CreatorRoyalties::fetch(
+            &self.asset.to_account_info(),
+            Some(&self.collection.to_account_info()),
+            creator_accounts,
+            None,
+        )?;
+
+        // pay fee to tresuary, royalties to creators and the escrowed offer to owner
+        let price_lamports = self.trait_offer.lamports;
+        let settlement = settle_sale(
+            &self.config,
+            &self.vault.to_account_info(),
+            &self.tresuary.to_account_info(),
+            &self.owner.to_account_info(),
+            None,
+            &royalties,
+            price_lamports,
+        )?;
+
+        // record sale in the collection stats
+        self.collection_stats.record(self.asset.key(), price_lamports, unix_timestamp);
+
+        // transfer asset to bidder, listed assets are frozen and can not be transferred
+        TransferV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.owner.to_account_info())
+            .authority(Some(&self.owner.to_account_info()))
+            .new_owner(&self.bidder.to_account_info())
+            .system_program(Some(&self.system_program.to_account_info()))
+            .invoke()?;
+
+        Ok(TraitOfferAccepted {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            asset: self.asset.key(),
+            trait_offer: self.trait_offer.key(),
+            seller: self.owner.key(),
+            buyer: self.bidder.key(),
+            price_lamports,
+            fee_lamports: settlement.fee_lamports(),
+            royalty_lamports: settlement.royalty_lamports,
+            seller_lamports: settlement.owner_lamports,
+            timestamp: unix_timestamp,
+        })
+    }
+}
@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{system_instruction::transfer, program::{invoke_signed, invoke}};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
 use mpl_core::{
     accounts::{BaseAssetV1, BaseCollectionV1}, 
     ID as CORE_PROGRAM_ID
 };
 
-use crate::{AssetAuction, AuctionErrors, CollectionAuction, Config};
+use crate::{AssetAuction, BidHistory, AuctionErrors, AuctionKind, BidRefund, CollectionAuction, Config, TokenVault, BidPlaced, outbid, transfer_to_vault};
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct BidAssetAuction<'info> {
     // EXTERNAL ACCOUNTS
@@ -15,14 +16,12 @@ pub struct BidAssetAuction<'info> {
     pub payer: Signer<'info>,
     /// CHECK: No signature or program ownership needed
     pub buyer: UncheckedAccount<'info>,
-    /// CHECK: No signature or program ownership needed
-    #[account(
-        mut,
-        address = asset_auction.buyer,
-    )]
-    pub previous_buyer: UncheckedAccount<'info>,
-    pub collection: Account<'info, BaseCollectionV1>,
+    // none for standalone assets of an update authority whitelist
+    #[account(address = collection_auction.collection @ AuctionErrors::InvalidCollection)]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
     pub asset: Account<'info, BaseAssetV1>,
+    /// CHECK: No signature or program ownership needed, frontend that brought the bid
+    pub referrer: Option<UncheckedAccount<'info>>,
 
     // INTERNAL ACCOUNTS
     #[account(
@@ -37,7 +36,7 @@ pub struct BidAssetAuction<'info> {
     )]
     pub vault: SystemAccount<'info>,
     #[account(
-        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
         bump = collection_auction.bump,
     )]
     pub collection_auction: Account<'info, CollectionAuction>,
@@ -47,79 +46,176 @@ pub struct BidAssetAuction<'info> {
         bump = asset_auction.bump,
     )]
     pub asset_auction: Account<'info, AssetAuction>,
+    #[account(
+        mut,
+        seeds = [b"bid_history", asset_auction.key().as_ref()],
+        bump = bid_history.bump,
+    )]
+    pub bid_history: Box<Account<'info, BidHistory>>,
+    // refund account of the outbid bidder, none for the opening bid
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"refund", asset_auction.key().as_ref(), asset_auction.buyer.as_ref(), asset_auction.funder.as_ref()],
+        bump,
+        space = 8 + BidRefund::INIT_SPACE,
+    )]
+    pub previous_bid_refund: Option<Account<'info, BidRefund>>,
+
+    // QUOTE MINT ACCOUNTS, only for collections bidding in a quote mint
+    #[account(
+        constraint = collection_auction.quote_mint == Some(quote_mint.key()) @ AuctionErrors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = token_program,
+    )]
+    pub payer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // GATE ACCOUNTS, only for gated collections
+    pub gate_asset: Option<Account<'info, BaseAssetV1>>,
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     // PROGRAMS
     pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
     /// CHECK: this will be checked by core
     #[account(address = CORE_PROGRAM_ID)]
     pub core_program: UncheckedAccount<'info>,
 }
 
 impl<'info> BidAssetAuction<'info> {
-    pub fn bid_asset_auction(&mut self, lamports: u64) -> Result<()> {
+    pub fn bid_asset_auction(&mut self, lamports: u64, proof: Vec<[u8; 32]>, bumps: &BidAssetAuctionBumps) -> Result<BidPlaced> {
+        require!(self.asset_auction.kind == AuctionKind::English, AuctionErrors::InvalidAuctionKind);
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        self.collection_auction.check_gate(&self.buyer.key(), self.gate_asset.as_deref(), self.gate_token_account.as_deref())?;
         require!(self.buyer.key() != self.asset_auction.owner, AuctionErrors::OwnerBid);
         require!(self.payer.key() != self.asset_auction.owner, AuctionErrors::OwnerBid);
         require!(lamports > self.asset_auction.buyer_bid_lamports, AuctionErrors::InvalidBid);
+        require!(self.asset_auction.is_allowed(&self.buyer.key(), &proof), AuctionErrors::NotAllowlisted);
+
+        // bidders can not refer their own bid, referrer shares are only paid in lamports
+        let referrer = self.referrer.as_ref().map(|referrer| referrer.key());
+        if let Some(referrer) = referrer {
+            require!(referrer != self.buyer.key() && referrer != self.payer.key(), AuctionErrors::InvalidReferrer);
+            require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+        }
 
         // seconds
         let unix_timestamp = Clock::get()?.unix_timestamp;
 
+        // scheduled auctions only take bids inside their window
+        if self.asset_auction.is_scheduled() {
+            require!(unix_timestamp >= self.asset_auction.start_timestamp, AuctionErrors::AuctionNotOpen);
+            require!(unix_timestamp < self.asset_auction.end_timestamp, AuctionErrors::AuctionEnded);
+        }
+
+        // previous bid, reported in the event
+        let previous_bidder = (self.asset_auction.first_bid_timestamp != 0).then_some(self.asset_auction.buyer);
+        let previous_bid_lamports = self.asset_auction.buyer_bid_lamports;
+
         // check if previous bid exists
-        match self.asset_auction.first_bid_timestamp {
+        let refund_lamports = match self.asset_auction.first_bid_timestamp {
             // no previous auction
             0 => {
+                // opening bid must reach the reserve
+                require!(lamports >= self.asset_auction.min_bid_lamports, AuctionErrors::BidBelowMinimum);
+                require!(self.previous_bid_refund.is_none(), AuctionErrors::InvalidBidRefund);
+
+                // first bid starts the auction clock, scheduled auctions keep their end
                 self.asset_auction.first_bid_timestamp = unix_timestamp;
+                if !self.asset_auction.is_scheduled() {
+                    self.asset_auction.end_timestamp = unix_timestamp + self.asset_auction.duration_minutes as i64 * 60;
+                }
+                0
             }
             // previous auction
             _ => { 
-                // check that auction is not over
-                let duration_minutes: i64 = (unix_timestamp - self.asset_auction.first_bid_timestamp) / 60;
-                require!(duration_minutes < self.asset_auction.duration_minutes as i64, AuctionErrors::AuctionEnded);
-
-                // transfer funds to previous buyer
-                let ix = transfer(
-                    &self.vault.key(),
-                    &self.previous_buyer.key(),
+                // min bid increment and anti-sniping extension
+                (self.asset_auction.end_timestamp, self.asset_auction.extensions) = outbid(
+                    &self.config,
+                    &self.collection_auction,
                     self.asset_auction.buyer_bid_lamports,
-                );
-        
-                let signer_seeds: [&[&[u8]]; 1] = [&[
-                    b"vault",
-                    self.config.to_account_info().key.as_ref(),
-                    &[self.config.vault_bump],
-                ]];
-        
-                invoke_signed(
-                    &ix,
-                    &[
-                        self.vault.to_account_info(),
-                        self.previous_buyer.to_account_info(),
-                    ],
-                    &signer_seeds,
+                    lamports,
+                    self.asset_auction.end_timestamp,
+                    self.asset_auction.extensions,
+                    unix_timestamp,
                 )?;
 
+                // credit previous bid to its refund account, funds stay in the vault
+                let previous_bid_refund = self.previous_bid_refund.as_mut().ok_or(AuctionErrors::InvalidBidRefund)?;
+                previous_bid_refund.credit(
+                    self.config.key(),
+                    self.asset_auction.key(),
+                    &self.asset_auction,
+                    self.collection_auction.quote_mint,
+                    self.payer.key(),
+                    bumps.previous_bid_refund,
+                );
+                previous_bid_refund.lamports
             }
         };
         
         // transfer new bid funds to vault
-        let ix = transfer(
-            &self.payer.key(),
-            &self.vault.key(),
-            lamports,
-        );
-
-        invoke(
-            &ix,
-            &[
-                self.payer.to_account_info(),
-                self.vault.to_account_info(),
-            ],
-        )?;
+        self.deposit_bid(lamports)?;
 
         // update buyer
         self.asset_auction.buyer = self.buyer.key();
+        self.asset_auction.funder = self.payer.key();
         self.asset_auction.buyer_bid_lamports = lamports;
+        self.asset_auction.bid_referrer = referrer;
+        self.bid_history.push(self.buyer.key(), lamports, unix_timestamp);
+
+        Ok(BidPlaced {
+            config: self.config.key(),
+            collection: self.asset_auction.collection,
+            asset: self.asset_auction.core_asset,
+            asset_auction: self.asset_auction.key(),
+            bidder: self.buyer.key(),
+            funder: self.payer.key(),
+            bid_lamports: lamports,
+            previous_bidder,
+            previous_bid_lamports,
+            refund_lamports,
+            referrer,
+            end_timestamp: self.asset_auction.end_timestamp,
+            timestamp: unix_timestamp,
+        })
+    }
+
+    // bids are paid in lamports or in the collection quote mint
+    fn deposit_bid(&self, amount: u64) -> Result<()> {
+        match self.collection_auction.quote_mint {
+            None => transfer_to_vault(&self.payer.to_account_info(), &self.vault.to_account_info(), amount),
+            Some(_) => {
+                let payer_token_account = self.payer_token_account.as_ref().ok_or(AuctionErrors::MissingTokenAccounts)?;
+                self.token_vault()?.deposit(payer_token_account, &self.payer.to_account_info(), amount)
+            }
+        }
+    }
 
-        Ok(())
+    fn token_vault(&self) -> Result<TokenVault<'_, 'info>> {
+        match (&self.quote_mint, &self.vault_token_account, &self.token_program) {
+            (Some(mint), Some(vault_token_account), Some(token_program)) => Ok(TokenVault {
+                config: &self.config,
+                vault: &self.vault,
+                mint,
+                vault_token_account,
+                token_program,
+            }),
+            _ => err!(AuctionErrors::MissingTokenAccounts),
+        }
     }
 }
\ No newline at end of file
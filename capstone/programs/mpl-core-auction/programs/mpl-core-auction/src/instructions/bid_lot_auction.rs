@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use mpl_core::accounts::BaseAssetV1;
+
+use crate::{BidRefund, CollectionAuction, Config, LotAuction, AuctionErrors, LotBidPlaced, outbid, transfer_to_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BidLotAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    pub buyer: UncheckedAccount<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), lot_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [b"lot", collection_auction.key().as_ref(), lot_auction.owner.as_ref(), lot_auction.seed.to_le_bytes().as_ref()],
+        bump = lot_auction.bump,
+    )]
+    pub lot_auction: Account<'info, LotAuction>,
+    // refund account of the outbid bidder, none for the opening bid
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"refund", lot_auction.key().as_ref(), lot_auction.buyer.as_ref(), lot_auction.funder.as_ref()],
+        bump,
+        space = 8 + BidRefund::INIT_SPACE,
+    )]
+    pub previous_bid_refund: Option<Account<'info, BidRefund>>,
+
+    // GATE ACCOUNTS, only for gated collections
+    pub gate_asset: Option<Account<'info, BaseAssetV1>>,
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> BidLotAuction<'info> {
+    pub fn bid_lot_auction(&mut self, lamports: u64, bumps: &BidLotAuctionBumps) -> Result<LotBidPlaced> {
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        self.collection_auction.check_gate(&self.buyer.key(), self.gate_asset.as_deref(), self.gate_token_account.as_deref())?;
+        require!(self.buyer.key() != self.lot_auction.owner, AuctionErrors::OwnerBid);
+        require!(self.payer.key() != self.lot_auction.owner, AuctionErrors::OwnerBid);
+        require!(lamports > self.lot_auction.buyer_bid_lamports, AuctionErrors::InvalidBid);
+
+        // seconds
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+
+        // previous bid, reported in the event
+        let previous_bidder = (self.lot_auction.first_bid_timestamp != 0).then_some(self.lot_auction.buyer);
+        let previous_bid_lamports = self.lot_auction.buyer_bid_lamports;
+
+        let refund_lamports = match self.lot_auction.first_bid_timestamp {
+            // opening bid must reach the reserve, it starts the auction clock
+            0 => {
+                require!(lamports >= self.lot_auction.min_bid_lamports, AuctionErrors::BidBelowMinimum);
+                require!(self.previous_bid_refund.is_none(), AuctionErrors::InvalidBidRefund);
+
+                self.lot_auction.first_bid_timestamp = unix_timestamp;
+                self.lot_auction.end_timestamp = unix_timestamp + self.lot_auction.duration_minutes as i64 * 60;
+                0
+            }
+            _ => {
+                // min bid increment and anti-sniping extension
+                (self.lot_auction.end_timestamp, self.lot_auction.extensions) = outbid(
+                    &self.config,
+                    &self.collection_auction,
+                    self.lot_auction.buyer_bid_lamports,
+                    lamports,
+                    self.lot_auction.end_timestamp,
+                    self.lot_auction.extensions,
+                    unix_timestamp,
+                )?;
+
+                // credit previous bid to its refund account, funds stay in the vault
+                let previous_bid_refund = self.previous_bid_refund.as_mut().ok_or(AuctionErrors::InvalidBidRefund)?;
+                previous_bid_refund.credit_lot(
+                    self.config.key(),
+                    self.lot_auction.key(),
+                    &self.lot_auction,
+                    self.payer.key(),
+                    bumps.previous_bid_refund,
+                );
+                previous_bid_refund.lamports
+            }
+        };
+
+        // transfer new bid funds to vault
+        transfer_to_vault(&self.payer.to_account_info(), &self.vault.to_account_info(), lamports)?;
+
+        // update buyer
+        self.lot_auction.buyer = self.buyer.key();
+        self.lot_auction.funder = self.payer.key();
+        self.lot_auction.buyer_bid_lamports = lamports;
+
+        Ok(LotBidPlaced {
+            config: self.config.key(),
+            collection: self.lot_auction.collection,
+            lot_auction: self.lot_auction.key(),
+            bidder: self.buyer.key(),
+            funder: self.payer.key(),
+            bid_lamports: lamports,
+            previous_bidder,
+            previous_bid_lamports,
+            refund_lamports,
+            end_timestamp: self.lot_auction.end_timestamp,
+            timestamp: unix_timestamp,
+        })
+    }
+}
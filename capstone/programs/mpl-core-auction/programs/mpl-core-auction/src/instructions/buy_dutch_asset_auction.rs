@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{AssetAuction, AuctionErrors, CollectionAuction, CollectionStats, Config, AssetCustody, CreatorRoyalties, AuctionCompleted, settle_sale, transfer_to_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuyDutchAssetAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    pub buyer: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"tresuary", config.key().as_ref()],
+        bump = config.tresuary_bump,
+    )]
+    pub tresuary: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [collection_auction.key().as_ref(), asset.key().as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+
+    // GATE ACCOUNTS, only for gated collections
+    pub gate_asset: Option<Account<'info, BaseAssetV1>>,
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> BuyDutchAssetAuction<'info> {
+    pub fn buy_dutch_asset_auction(&mut self, max_price_lamports: u64, creator_accounts: &[AccountInfo<'info>]) -> Result<AuctionCompleted> {
+        require!(self.buyer.key() != self.asset_auction.owner, AuctionErrors::OwnerBid);
+        require!(self.payer.key() != self.asset_auction.owner, AuctionErrors::OwnerBid);
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        self.collection_auction.check_gate(&self.buyer.key(), self.gate_asset.as_deref(), self.gate_token_account.as_deref())?;
+
+        // current price, validates auction kind
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        let price_lamports = self.asset_auction
+            .dutch_price(unix_timestamp)
+            .ok_or(AuctionErrors::InvalidAuctionKind)?;
+        require!(price_lamports <= max_price_lamports, AuctionErrors::PriceAboveMax);
+
+        // asset auction is closed, collection open auctions counter goes down
+        self.collection_auction.close_auction()?;
+
+        // transfer price to vault
+        transfer_to_vault(&self.payer.to_account_info(), &self.vault.to_account_info(), price_lamports)?;
+
+        // creator royalties, creator accounts are passed as remaining accounts
+        let royalties = CreatorRoyalties::fetch(
+            &self.asset.to_account_info(),
+            Some(&self.collection.to_account_info()),
+            creator_accounts,
+            None,
+        )?;
+
+        // pay fee to tresuary, royalties to creators and funds to owner
+        let settlement = settle_sale(
+            &self.config,
+            &self.vault.to_account_info(),
+            &self.tresuary.to_account_info(),
+            &self.owner.to_account_info(),
+            None,
+            &royalties,
+            price_lamports,
+        )?;
+
+        // record sale in the collection stats
+        self.collection_stats.record(self.asset.key(), price_lamports, unix_timestamp);
+
+        // modify asset signer seeds
+        let modify_asset_signer_seeds: [&[&[u8]]; 1] = [&[
+            self.collection_auction.to_account_info().key.as_ref(),
+            self.asset.to_account_info().key.as_ref(),
+            &[self.asset_auction.bump],
+        ]];
+
+        // thaw asset and transfer it to buyer
+        AssetCustody {
+            core_program: &self.core_program.to_account_info(),
+            asset: &self.asset.to_account_info(),
+            collection: Some(&self.collection.to_account_info()),
+            payer: &self.payer.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            authority: &self.asset_auction.to_account_info(),
+        }.release(&self.buyer.to_account_info(), &modify_asset_signer_seeds)?;
+
+        AuctionCompleted::new(self.config.key(), &self.asset_auction, self.buyer.key(), None, price_lamports, &settlement)
+    }
+}
\ No newline at end of file
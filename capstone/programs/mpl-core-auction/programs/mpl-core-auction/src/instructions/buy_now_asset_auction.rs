@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{AssetAuction, BidHistory, AuctionErrors, AuctionKind, BidRefund, CollectionAuction, CollectionStats, Config, AssetCustody, CreatorRoyalties, Referrers, AuctionCompleted, settle_referred_sale, transfer_to_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuyNowAssetAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    pub buyer: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    // none for standalone assets of an update authority whitelist
+    #[account(mut)]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+    /// CHECK: No signature or program ownership needed, referrer of the listing
+    #[account(mut)]
+    pub listing_referrer: Option<UncheckedAccount<'info>>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"tresuary", config.key().as_ref()],
+        bump = config.tresuary_bump,
+    )]
+    pub tresuary: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [collection_auction.key().as_ref(), asset.key().as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"bid_history", asset_auction.key().as_ref()],
+        bump = bid_history.bump,
+    )]
+    pub bid_history: Box<Account<'info, BidHistory>>,
+    // refund account of the outbid bidder, none when there is no bid
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"refund", asset_auction.key().as_ref(), asset_auction.buyer.as_ref(), asset_auction.funder.as_ref()],
+        bump,
+        space = 8 + BidRefund::INIT_SPACE,
+    )]
+    pub previous_bid_refund: Option<Account<'info, BidRefund>>,
+
+    // GATE ACCOUNTS, only for gated collections
+    pub gate_asset: Option<Account<'info, BaseAssetV1>>,
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> BuyNowAssetAuction<'info> {
+    pub fn buy_now_asset_auction(&mut self, proof: Vec<[u8; 32]>, creator_accounts: &[AccountInfo<'info>], bumps: &BuyNowAssetAuctionBumps) -> Result<AuctionCompleted> {
+        require!(self.asset_auction.kind == AuctionKind::English, AuctionErrors::InvalidAuctionKind);
+        require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        self.collection_auction.check_gate(&self.buyer.key(), self.gate_asset.as_deref(), self.gate_token_account.as_deref())?;
+        require!(self.buyer.key() != self.asset_auction.owner, AuctionErrors::OwnerBid);
+        require!(self.payer.key() != self.asset_auction.owner, AuctionErrors::OwnerBid);
+        require!(self.asset_auction.is_allowed(&self.buyer.key(), &proof), AuctionErrors::NotAllowlisted);
+        let collection = self.collection.as_ref().map(|collection| collection.to_account_info());
+        self.collection_auction.check_collection(collection.as_ref())?;
+
+        // listing referrer recorded on the auction has to be passed to get its share
+        require!(self.listing_referrer.as_ref().map(|listing_referrer| listing_referrer.key()) == self.asset_auction.listing_referrer, AuctionErrors::InvalidReferrer);
+
+        // buy now price, disabled once bidding passes the cutoff
+        let price_lamports = self.asset_auction.buy_now_price().ok_or(AuctionErrors::BuyNowUnavailable)?;
+
+        // asset auction is closed, collection open auctions counter goes down
+        self.collection_auction.close_auction()?;
+
+        // scheduled auctions only sell inside their window
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        if self.asset_auction.is_scheduled() {
+            require!(unix_timestamp >= self.asset_auction.start_timestamp, AuctionErrors::AuctionNotOpen);
+            require!(unix_timestamp < self.asset_auction.end_timestamp, AuctionErrors::AuctionEnded);
+        }
+
+        // check that auction is not over
+        if self.asset_auction.first_bid_timestamp != 0 {
+            require!(unix_timestamp < self.asset_auction.end_timestamp, AuctionErrors::AuctionEnded);
+
+            // credit current high bid to its refund account, funds stay in the vault
+            let previous_bid_refund = self.previous_bid_refund.as_mut().ok_or(AuctionErrors::InvalidBidRefund)?;
+            previous_bid_refund.credit(
+                self.config.key(),
+                self.asset_auction.key(),
+                &self.asset_auction,
+                None,
+                self.payer.key(),
+                bumps.previous_bid_refund,
+            );
+        } else {
+            require!(self.previous_bid_refund.is_none(), AuctionErrors::InvalidBidRefund);
+        }
+
+        // transfer buy now price to vault
+        transfer_to_vault(&self.payer.to_account_info(), &self.vault.to_account_info(), price_lamports)?;
+
+        // creator royalties, creator accounts are passed as remaining accounts
+        let royalties = CreatorRoyalties::fetch(
+            &self.asset.to_account_info(),
+            collection.as_ref(),
+            creator_accounts,
+            None,
+        )?;
+
+        // pay fee to tresuary, share to the listing referrer, royalties to creators and funds to owner
+        let listing_referrer = self.listing_referrer.as_ref().map(|listing_referrer| listing_referrer.to_account_info());
+        let settlement = settle_referred_sale(
+            &self.config,
+            &self.vault.to_account_info(),
+            &self.tresuary.to_account_info(),
+            &self.owner.to_account_info(),
+            None,
+            &Referrers {
+                bid: None,
+                listing: listing_referrer.as_ref(),
+            },
+            &royalties,
+            price_lamports,
+        )?;
+
+        // record sale in the collection stats
+        self.collection_stats.record(self.asset.key(), price_lamports, unix_timestamp);
+
+        // modify asset signer seeds
+        let modify_asset_signer_seeds: [&[&[u8]]; 1] = [&[
+            self.collection_auction.to_account_info().key.as_ref(),
+            self.asset.to_account_info().key.as_ref(),
+            &[self.asset_auction.bump],
+        ]];
+
+        // thaw asset and transfer it to buyer
+        AssetCustody {
+            core_program: &self.core_program.to_account_info(),
+            asset: &self.asset.to_account_info(),
+            collection: collection.as_ref(),
+            payer: &self.payer.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            authority: &self.asset_auction.to_account_info(),
+        }.release(&self.buyer.to_account_info(), &modify_asset_signer_seeds)?;
+
+        AuctionCompleted::new(self.config.key(), &self.asset_auction, self.buyer.key(), None, price_lamports, &settlement)
+    }
+}
\ No newline at end of file
@@ -1,15 +1,15 @@
 use anchor_lang::prelude::*;
 use mpl_core::{
     accounts::{BaseAssetV1, BaseCollectionV1}, 
-    instructions::{RemovePluginV1CpiBuilder, UpdatePluginV1CpiBuilder, RevokePluginAuthorityV1CpiBuilder}, 
-    types::{PluginType, FreezeDelegate, Plugin }, ID as CORE_PROGRAM_ID
+    ID as CORE_PROGRAM_ID
 };
 
 
 
-use crate::{CollectionAuction, AssetAuction, AuctionErrors, Config};
+use crate::{CollectionAuction, AssetAuction, BidHistory, AuctionErrors, AuctionKind, Config, AssetCustody, AuctionCancelled};
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CancelAssetAuction<'info> {
     // EXTERNAL ACCOUNTS
@@ -17,8 +17,9 @@ pub struct CancelAssetAuction<'info> {
     pub payer: Signer<'info>,
     #[account(mut)]
     pub owner: Signer<'info>,
+    // none for standalone assets of an update authority whitelist
     #[account(mut)]
-    pub collection: Account<'info, BaseCollectionV1>,
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
     #[account(
         mut,
         has_one = owner,
@@ -32,7 +33,8 @@ pub struct CancelAssetAuction<'info> {
     )]
     pub config: Account<'info, Config>,
     #[account(
-        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
         bump = collection_auction.bump,
     )]
     pub collection_auction: Account<'info, CollectionAuction>,
@@ -43,6 +45,14 @@ pub struct CancelAssetAuction<'info> {
         bump = asset_auction.bump,
     )]
     pub asset_auction: Account<'info, AssetAuction>,
+    // english auctions only, other kinds have no bid history
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"bid_history", asset_auction.key().as_ref()],
+        bump = bid_history.bump,
+    )]
+    pub bid_history: Option<Box<Account<'info, BidHistory>>>,
 
     // PROGRAMS
     pub system_program: Program<'info, System>,
@@ -52,9 +62,15 @@ pub struct CancelAssetAuction<'info> {
 }
 
 impl<'info> CancelAssetAuction<'info> {
-    pub fn cancel_asset_auction(&mut self) -> Result<()> {
+    pub fn cancel_asset_auction(&mut self) -> Result<AuctionCancelled> {
         // validate auction has not started
         require!(self.asset_auction.first_bid_timestamp == 0, AuctionErrors::AuctionStarted);
+        require!(self.asset_auction.kind != AuctionKind::English || self.bid_history.is_some(), AuctionErrors::MissingBidHistory);
+        let collection = self.collection.as_ref().map(|collection| collection.to_account_info());
+        self.collection_auction.check_collection(collection.as_ref())?;
+
+        // asset auction is closed, collection open auctions counter goes down
+        self.collection_auction.close_auction()?;
 
         // set program signer seeds
         let signer_seeds: [&[&[u8]]; 1] = [&[
@@ -63,47 +79,17 @@ impl<'info> CancelAssetAuction<'info> {
             &[self.asset_auction.bump],
         ]];
 
-        // thaw asset
-        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.payer.to_account_info())
-            .authority(Some(&self.asset_auction.to_account_info()))
-            .system_program(&self.system_program.to_account_info())
-            .plugin(Plugin::FreezeDelegate( FreezeDelegate { frozen: false } ))
-            .invoke_signed(&signer_seeds)?;
-  
-        // remove freeze delegate plugin
-        RemovePluginV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.payer.to_account_info())
-            .authority(Some(&self.owner.to_account_info()))
-            .system_program(&self.system_program.to_account_info())
-            .plugin_type(PluginType::FreezeDelegate)
-            .invoke()?;
-
-        // revoke transfer delegate
-        RevokePluginAuthorityV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.payer.to_account_info())
-            .authority(Some(&self.asset_auction.to_account_info()))
-            .system_program(&self.system_program.to_account_info())
-            .plugin_type(PluginType::TransferDelegate)
-            .invoke_signed(&signer_seeds)?;
-        
-        // remove transfer delegate plugin
-        RemovePluginV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.payer.to_account_info())
-            .authority(Some(&self.owner.to_account_info()))
-            .system_program(&self.system_program.to_account_info())
-            .plugin_type(PluginType::TransferDelegate)
-            .invoke()?;
+        // thaw asset and remove auction plugins
+        AssetCustody {
+            core_program: &self.core_program.to_account_info(),
+            asset: &self.asset.to_account_info(),
+            collection: collection.as_ref(),
+            payer: &self.payer.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            authority: &self.asset_auction.to_account_info(),
+        }.unlock(&self.owner.to_account_info(), &signer_seeds)?;
 
-        Ok(())
+        AuctionCancelled::new(self.config.key(), &self.asset_auction)
     }
 
 }
\ No newline at end of file
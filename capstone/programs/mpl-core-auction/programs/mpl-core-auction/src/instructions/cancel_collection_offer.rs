@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{CollectionOffer, Config, CollectionOfferCancelled, transfer_from_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelCollectionOffer<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = config,
+        has_one = bidder,
+        seeds = [b"collection_offer", config.key().as_ref(), collection_offer.collection.as_ref(), bidder.key().as_ref()],
+        bump = collection_offer.bump,
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> CancelCollectionOffer<'info> {
+    pub fn cancel_collection_offer(&mut self) -> Result<CollectionOfferCancelled> {
+        // lamports of the unfilled units go back to the bidder
+        let refund_lamports = self.collection_offer.lamports * self.collection_offer.remaining() as u64;
+        transfer_from_vault(&self.config, &self.vault.to_account_info(), &self.bidder.to_account_info(), refund_lamports)?;
+
+        Ok(CollectionOfferCancelled {
+            config: self.config.key(),
+            collection: self.collection_offer.collection,
+            collection_offer: self.collection_offer.key(),
+            bidder: self.bidder.key(),
+            refund_lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
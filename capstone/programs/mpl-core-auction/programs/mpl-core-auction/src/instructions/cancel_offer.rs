@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, Offer, OfferCancelled, transfer_from_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = config,
+        has_one = bidder,
+        seeds = [b"offer", config.key().as_ref(), offer.asset.as_ref(), bidder.key().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> CancelOffer<'info> {
+    pub fn cancel_offer(&mut self) -> Result<OfferCancelled> {
+        // escrowed lamports go back to the bidder, expired offers included
+        transfer_from_vault(&self.config, &self.vault.to_account_info(), &self.bidder.to_account_info(), self.offer.lamports)?;
+
+        Ok(OfferCancelled {
+            config: self.config.key(),
+            collection: self.offer.collection,
+            asset: self.offer.asset,
+            offer: self.offer.key(),
+            bidder: self.bidder.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
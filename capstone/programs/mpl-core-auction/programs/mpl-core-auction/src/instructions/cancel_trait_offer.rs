@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, TraitOffer, TraitOfferCancelled, transfer_from_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelTraitOffer<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = config,
+        has_one = bidder,
+        seeds = [b"trait_offer", trait_offer.collection_auction.as_ref(), bidder.key().as_ref(), trait_offer.seed.to_le_bytes().as_ref()],
+        bump = trait_offer.bump,
+    )]
+    pub trait_offer: Account<'info, TraitOffer>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> CancelTraitOffer<'info> {
+    pub fn cancel_trait_offer(&mut self) -> Result<TraitOfferCancelled> {
+        // escrowed lamports go back to the bidder, expired offers included
+        transfer_from_vault(&self.config, &self.vault.to_account_info(), &self.bidder.to_account_info(), self.trait_offer.lamports)?;
+
+        Ok(TraitOfferCancelled {
+            config: self.config.key(),
+            collection: self.trait_offer.collection,
+            trait_offer: self.trait_offer.key(),
+            bidder: self.bidder.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
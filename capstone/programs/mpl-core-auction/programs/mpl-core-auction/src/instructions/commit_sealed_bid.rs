@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use mpl_core::accounts::{BaseAssetV1, BaseCollectionV1};
+
+use crate::{AssetAuction, AuctionErrors, AuctionKind, CollectionAuction, Config, SealedBid, SealedBidCommitted, transfer_to_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CommitSealedBid<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    pub bidder: UncheckedAccount<'info>,
+    pub collection: Account<'info, BaseCollectionV1>,
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [collection_auction.key().as_ref(), asset.key().as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"sealed_bid", asset_auction.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = 8 + SealedBid::INIT_SPACE,
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+
+    // GATE ACCOUNTS, only for gated collections
+    pub gate_asset: Option<Account<'info, BaseAssetV1>>,
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitSealedBid<'info> {
+    pub fn commit_sealed_bid(&mut self, commitment: [u8; 32], deposit_lamports: u64, bumps: &CommitSealedBidBumps) -> Result<SealedBidCommitted> {
+        require!(self.bidder.key() != self.asset_auction.owner, AuctionErrors::OwnerBid);
+        require!(self.payer.key() != self.asset_auction.owner, AuctionErrors::OwnerBid);
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        self.collection_auction.check_gate(&self.bidder.key(), self.gate_asset.as_deref(), self.gate_token_account.as_deref())?;
+
+        // validate auction kind and commit phase
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        let AuctionKind::Sealed { commit_end_timestamp, .. } = self.asset_auction.kind else {
+            return err!(AuctionErrors::InvalidAuctionKind);
+        };
+        require!(unix_timestamp < commit_end_timestamp, AuctionErrors::CommitPhaseOver);
+
+        // deposit has to cover the reserve, it hides the real bid
+        require!(deposit_lamports >= self.asset_auction.min_bid_lamports, AuctionErrors::BidBelowMinimum);
+
+        // track open bids, the auction is closed once all of them are settled
+        if let AuctionKind::Sealed { ref mut open_bids, .. } = self.asset_auction.kind {
+            *open_bids += 1;
+        }
+
+        // first commit starts the auction, it can not be cancelled anymore
+        if self.asset_auction.first_bid_timestamp == 0 {
+            self.asset_auction.first_bid_timestamp = unix_timestamp;
+        }
+
+        // transfer deposit to vault
+        transfer_to_vault(&self.payer.to_account_info(), &self.vault.to_account_info(), deposit_lamports)?;
+
+        self.sealed_bid.set_inner(SealedBid {
+            asset_auction: self.asset_auction.key(),
+            bidder: self.bidder.key(),
+            funder: self.payer.key(),
+            commitment,
+            deposit_lamports,
+            revealed_lamports: 0,
+            revealed: false,
+            bump: bumps.sealed_bid,
+        });
+
+        Ok(SealedBidCommitted {
+            config: self.config.key(),
+            collection: self.asset_auction.collection,
+            asset: self.asset_auction.core_asset,
+            asset_auction: self.asset_auction.key(),
+            bidder: self.bidder.key(),
+            funder: self.payer.key(),
+            deposit_lamports,
+            timestamp: unix_timestamp,
+        })
+    }
+}
\ No newline at end of file
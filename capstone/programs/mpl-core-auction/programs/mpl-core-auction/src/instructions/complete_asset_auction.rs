@@ -1,17 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{system_instruction::transfer, program::invoke_signed};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
 use mpl_core::{
     accounts::{BaseAssetV1, BaseCollectionV1}, 
-    instructions::{UpdatePluginV1CpiBuilder, TransferV1CpiBuilder},
-    types::{Plugin, FreezeDelegate},
     ID as CORE_PROGRAM_ID
 };
 
 
 
-use crate::{AssetAuction, AuctionErrors, CollectionAuction, Config};
+use crate::{AssetAuction, BidHistory, AuctionErrors, AuctionKind, CollectionAuction, CollectionStats, Config, AssetCustody, CreatorRoyalties, Referrers, TokenVault, AuctionCompleted, settle_referred_sale};
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CompleteAssetAuction<'info> {
     // EXTERNAL ACCOUNTS
@@ -21,14 +20,22 @@ pub struct CompleteAssetAuction<'info> {
     #[account(mut)]
     pub owner: UncheckedAccount<'info>,
     /// CHECK: No signature or program ownership needed
+    #[account(address = asset_auction.buyer)]
     pub buyer: UncheckedAccount<'info>,
+    // none for standalone assets of an update authority whitelist
     #[account(mut)]
-    pub collection: Account<'info, BaseCollectionV1>,
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
     #[account(
         mut,
         has_one = owner,
     )]
     pub asset: Account<'info, BaseAssetV1>,
+    /// CHECK: No signature or program ownership needed, referrer of the winning bid
+    #[account(mut)]
+    pub bid_referrer: Option<UncheckedAccount<'info>>,
+    /// CHECK: No signature or program ownership needed, referrer of the listing
+    #[account(mut)]
+    pub listing_referrer: Option<UncheckedAccount<'info>>,
 
     // INTERNAL ACCOUNTS
     #[account(
@@ -49,10 +56,17 @@ pub struct CompleteAssetAuction<'info> {
     )]
     pub vault: SystemAccount<'info>,
     #[account(
-        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
         bump = collection_auction.bump,
     )]
     pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
     #[account(
         mut,
         close = owner,
@@ -60,68 +74,131 @@ pub struct CompleteAssetAuction<'info> {
         bump = asset_auction.bump,
     )]
     pub asset_auction: Account<'info, AssetAuction>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"bid_history", asset_auction.key().as_ref()],
+        bump = bid_history.bump,
+    )]
+    pub bid_history: Box<Account<'info, BidHistory>>,
+
+    // QUOTE MINT ACCOUNTS, only for collections bidding in a quote mint
+    #[account(
+        constraint = collection_auction.quote_mint == Some(quote_mint.key()) @ AuctionErrors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = tresuary,
+        associated_token::token_program = token_program,
+    )]
+    pub tresuary_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    // keeper reward token account, the reward stays in the tresuary without it
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = token_program,
+    )]
+    pub payer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     // PROGRAMS
     pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
     /// CHECK: this will be checked by core
     #[account(address = CORE_PROGRAM_ID)]
     pub core_program: UncheckedAccount<'info>,
 }
 
 impl<'info> CompleteAssetAuction<'info> {
-    pub fn complete_asset_auction(&mut self) -> Result<()> {
+    pub fn complete_asset_auction(&mut self, creator_accounts: &[AccountInfo<'info>]) -> Result<AuctionCompleted> {
+        // validate auction kind
+        require!(self.asset_auction.kind == AuctionKind::English, AuctionErrors::InvalidAuctionKind);
+
         // validate auction has started
         require!(self.asset_auction.first_bid_timestamp != 0, AuctionErrors::AuctionNotStarted);
 
         // validate auction is over
         let unix_timestamp = Clock::get()?.unix_timestamp;
-        let duration_minutes: i64 = (unix_timestamp - self.asset_auction.first_bid_timestamp) / 60;
-        require!(duration_minutes >= self.asset_auction.duration_minutes as i64, AuctionErrors::AuctionRunning);
-
-        // calculate owner and vault lamprots
-        let fee_decimal = (self.config.fee_bps as f64) / 10_000_f64;
-        let buyer_bid_lamports = self.asset_auction.buyer_bid_lamports as f64;
-        let treusary_lamports = (buyer_bid_lamports * fee_decimal).ceil() as u64;
-        let owner_lamports = (buyer_bid_lamports * (1.0 - fee_decimal)).floor()  as u64;
-
-        // transfer sol signer seeds
-        let transfer_sol_signer_seeds: [&[&[u8]]; 1] = [&[
-            b"vault",
-            self.config.to_account_info().key.as_ref(),
-            &[self.config.vault_bump],
-        ]];
-        
-        // transfer fee to tresuary 
-        let ix = transfer(
-            &self.vault.key(),
-            &self.tresuary.key(),
-            treusary_lamports,
-        );
-
-        invoke_signed(
-            &ix,
-            &[
-                self.vault.to_account_info(),
-                self.tresuary.to_account_info(),
-            ],
-            &transfer_sol_signer_seeds,
+        require!(unix_timestamp >= self.asset_auction.end_timestamp, AuctionErrors::AuctionRunning);
+
+        // standalone assets are settled without a collection account
+        let collection = self.collection.as_ref().map(|collection| collection.to_account_info());
+        self.collection_auction.check_collection(collection.as_ref())?;
+
+        // referrers recorded on the auction have to be passed to get their share
+        require!(self.bid_referrer.as_ref().map(|bid_referrer| bid_referrer.key()) == self.asset_auction.bid_referrer, AuctionErrors::InvalidReferrer);
+        require!(self.listing_referrer.as_ref().map(|listing_referrer| listing_referrer.key()) == self.asset_auction.listing_referrer, AuctionErrors::InvalidReferrer);
+
+        // asset auction is closed, collection open auctions counter goes down
+        self.collection_auction.close_auction()?;
+
+        // creator royalties, creator accounts are passed as remaining accounts
+        let token_program = self.token_program.as_ref().map(|token_program| token_program.key());
+        let royalties = CreatorRoyalties::fetch(
+            &self.asset.to_account_info(),
+            collection.as_ref(),
+            creator_accounts,
+            self.collection_auction.quote_mint.zip(token_program),
         )?;
 
-        // Transfer funds to owner
-        let ix = transfer(
-            &self.vault.key(),
-            &self.owner.key(),
-            owner_lamports,
-        );
-
-        invoke_signed(
-            &ix,
-            &[
-                self.vault.to_account_info(),
-                self.owner.to_account_info(),
-            ],
-            &transfer_sol_signer_seeds,
-        )?;
+        // pay fee to tresuary, reward to keeper, shares to referrers, royalties to creators and funds to owner,
+        // in lamports or in the collection quote mint, quote mint auctions never record referrers
+        let bid_referrer = self.bid_referrer.as_ref().map(|bid_referrer| bid_referrer.to_account_info());
+        let listing_referrer = self.listing_referrer.as_ref().map(|listing_referrer| listing_referrer.to_account_info());
+        let settlement = match self.collection_auction.quote_mint {
+            None => settle_referred_sale(
+                &self.config,
+                &self.vault.to_account_info(),
+                &self.tresuary.to_account_info(),
+                &self.owner.to_account_info(),
+                Some(&self.payer.to_account_info()),
+                &Referrers {
+                    bid: bid_referrer.as_ref(),
+                    listing: listing_referrer.as_ref(),
+                },
+                &royalties,
+                self.asset_auction.buyer_bid_lamports,
+            )?,
+            Some(_) => match (
+                &self.quote_mint,
+                &self.vault_token_account,
+                &self.tresuary_token_account,
+                &self.owner_token_account,
+                &self.token_program,
+            ) {
+                (Some(mint), Some(vault_token_account), Some(tresuary_token_account), Some(owner_token_account), Some(token_program)) => TokenVault {
+                    config: &self.config,
+                    vault: &self.vault,
+                    mint,
+                    vault_token_account,
+                    token_program,
+                }.settle_sale(tresuary_token_account, owner_token_account, self.payer_token_account.as_ref(), &royalties, self.asset_auction.buyer_bid_lamports)?,
+                _ => return err!(AuctionErrors::MissingTokenAccounts),
+            },
+        };
+
+        // record sale in the collection stats
+        self.collection_stats.record(self.asset.key(), self.asset_auction.buyer_bid_lamports, unix_timestamp);
 
         // modify asset signer seeds
         let modify_asset_signer_seeds: [&[&[u8]]; 1] = [&[
@@ -130,26 +207,23 @@ impl<'info> CompleteAssetAuction<'info> {
             &[self.asset_auction.bump],
         ]];
 
-        // thaw asset
-        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.payer.to_account_info())
-            .authority(Some(&self.asset_auction.to_account_info()))
-            .system_program(&self.system_program.to_account_info())
-            .plugin(Plugin::FreezeDelegate( FreezeDelegate { frozen: false } ))
-            .invoke_signed(&modify_asset_signer_seeds)?;
-
-        // transfer asset to owner
-        TransferV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.payer.to_account_info())
-            .authority(Some(&self.asset_auction.to_account_info()))
-            .new_owner(&self.buyer.to_account_info())
-            .system_program(Some(&self.system_program.to_account_info()))
-            .invoke_signed(&modify_asset_signer_seeds)?;
-
-        Ok(())
+        // thaw asset and transfer it to buyer
+        AssetCustody {
+            core_program: &self.core_program.to_account_info(),
+            asset: &self.asset.to_account_info(),
+            collection: collection.as_ref(),
+            payer: &self.payer.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            authority: &self.asset_auction.to_account_info(),
+        }.release(&self.buyer.to_account_info(), &modify_asset_signer_seeds)?;
+
+        AuctionCompleted::new(
+            self.config.key(),
+            &self.asset_auction,
+            self.buyer.key(),
+            self.collection_auction.quote_mint,
+            self.asset_auction.buyer_bid_lamports,
+            &settlement,
+        )
     }
 }
\ No newline at end of file
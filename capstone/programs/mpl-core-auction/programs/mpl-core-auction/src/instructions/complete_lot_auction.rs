@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseCollectionV1,
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{CollectionAuction, CollectionStats, Config, LotAuction, AuctionErrors, AssetCustody, CreatorRoyalties, LotCompleted, same_royalties, settle_sale};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompleteLotAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(address = lot_auction.buyer)]
+    pub buyer: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"tresuary", config.key().as_ref()],
+        bump = config.tresuary_bump,
+    )]
+    pub tresuary: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        has_one = collection,
+        seeds = [b"lot", collection_auction.key().as_ref(), owner.key().as_ref(), lot_auction.seed.to_le_bytes().as_ref()],
+        bump = lot_auction.bump,
+    )]
+    pub lot_auction: Account<'info, LotAuction>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+
+impl<'info> CompleteLotAuction<'info> {
+    // remaining accounts are the lot assets in order followed by the creator accounts
+    pub fn complete_lot_auction(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<LotCompleted> {
+        // validate auction has started and is over
+        require!(self.lot_auction.first_bid_timestamp != 0, AuctionErrors::AuctionNotStarted);
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        require!(unix_timestamp >= self.lot_auction.end_timestamp, AuctionErrors::AuctionRunning);
+
+        require!(remaining_accounts.len() >= self.lot_auction.assets.len(), AuctionErrors::InvalidLotAssets);
+        let (asset_accounts, creator_accounts) = remaining_accounts.split_at(self.lot_auction.assets.len());
+        require!(self.lot_auction.check_assets(asset_accounts), AuctionErrors::InvalidLotAssets);
+
+        // lot auction is closed, collection open auctions counter goes down
+        self.collection_auction.close_auction()?;
+
+        // every lot asset must still share the same royalties, the update authority can change them after creation
+        require!(same_royalties(asset_accounts, Some(&self.collection.to_account_info())), AuctionErrors::LotRoyaltiesMismatch);
+        let royalties = CreatorRoyalties::fetch(
+            &asset_accounts[0],
+            Some(&self.collection.to_account_info()),
+            creator_accounts,
+            None,
+        )?;
+
+        // pay fee to tresuary, reward to keeper, royalties to creators and funds to owner
+        let price_lamports = self.lot_auction.buyer_bid_lamports;
+        let settlement = settle_sale(
+            &self.config,
+            &self.vault.to_account_info(),
+            &self.tresuary.to_account_info(),
+            &self.owner.to_account_info(),
+            Some(&self.payer.to_account_info()),
+            &royalties,
+            price_lamports,
+        )?;
+
+        // record a sale per asset in the collection stats, the price is split evenly
+        // and the last asset takes the rounding remainder
+        let asset_count = self.lot_auction.assets.len() as u64;
+        let asset_price_lamports = price_lamports / asset_count;
+        for (i, asset) in self.lot_auction.assets.iter().enumerate() {
+            let sale_lamports = match i as u64 == asset_count - 1 {
+                true => price_lamports - asset_price_lamports * (asset_count - 1),
+                false => asset_price_lamports,
+            };
+            self.collection_stats.record(*asset, sale_lamports, unix_timestamp);
+        }
+
+        // set program signer seeds
+        let collection_auction_key = self.collection_auction.key();
+        let owner_key = self.owner.key();
+        let seed_bytes = self.lot_auction.seed.to_le_bytes();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"lot",
+            collection_auction_key.as_ref(),
+            owner_key.as_ref(),
+            seed_bytes.as_ref(),
+            &[self.lot_auction.bump],
+        ]];
+
+        // thaw every asset and transfer it to buyer
+        for asset_account in asset_accounts {
+            AssetCustody {
+                core_program: &self.core_program.to_account_info(),
+                asset: asset_account,
+                collection: Some(&self.collection.to_account_info()),
+                payer: &self.payer.to_account_info(),
+                system_program: &self.system_program.to_account_info(),
+                authority: &self.lot_auction.to_account_info(),
+            }.release(&self.buyer.to_account_info(), &signer_seeds)?;
+        }
+
+        Ok(LotCompleted {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            lot_auction: self.lot_auction.key(),
+            assets: self.lot_auction.assets.clone(),
+            seller: self.owner.key(),
+            buyer: self.buyer.key(),
+            price_lamports,
+            fee_lamports: settlement.fee_lamports(),
+            keeper_lamports: settlement.keeper_lamports,
+            royalty_lamports: settlement.royalty_lamports,
+            seller_lamports: settlement.owner_lamports,
+            timestamp: unix_timestamp,
+        })
+    }
+}
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{AssetAuction, AuctionErrors, AuctionKind, CollectionAuction, CollectionStats, Config, SealedBid, AssetCustody, CreatorRoyalties, SealedSettlement, AuctionCompleted, AuctionCancelled, settle_sale, transfer_from_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompleteSealedAssetAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = asset_auction.buyer,
+    )]
+    pub buyer: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = asset_auction.funder,
+    )]
+    pub funder: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"tresuary", config.key().as_ref()],
+        bump = config.tresuary_bump,
+    )]
+    pub tresuary: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [collection_auction.key().as_ref(), asset.key().as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+    // winner sealed bid, none when no bid reached the reserve
+    #[account(
+        mut,
+        close = funder,
+        has_one = asset_auction,
+        seeds = [b"sealed_bid", asset_auction.key().as_ref(), asset_auction.buyer.as_ref()],
+        bump = winner_bid.bump,
+    )]
+    pub winner_bid: Option<Account<'info, SealedBid>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> CompleteSealedAssetAuction<'info> {
+    pub fn complete_sealed_asset_auction(&mut self, creator_accounts: &[AccountInfo<'info>]) -> Result<SealedSettlement> {
+        // validate auction kind and reveal phase is over
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        let AuctionKind::Sealed { settled, .. } = self.asset_auction.kind else {
+            return err!(AuctionErrors::InvalidAuctionKind);
+        };
+        require!(!settled, AuctionErrors::AuctionSettled);
+        require!(unix_timestamp >= self.asset_auction.end_timestamp, AuctionErrors::RevealPhaseRunning);
+
+        // modify asset signer seeds
+        let modify_asset_signer_seeds: [&[&[u8]]; 1] = [&[
+            self.collection_auction.to_account_info().key.as_ref(),
+            self.asset.to_account_info().key.as_ref(),
+            &[self.asset_auction.bump],
+        ]];
+
+        let settlement = if self.asset_auction.buyer != self.asset_auction.owner {
+            let winner_bid = self.winner_bid.as_ref().ok_or(AuctionErrors::MissingWinnerBid)?;
+
+            // winner pays the second price, the rest of the deposit is refunded to its funder
+            let price_lamports = self.asset_auction.sealed_price().ok_or(AuctionErrors::InvalidAuctionKind)?;
+            transfer_from_vault(
+                &self.config,
+                &self.vault.to_account_info(),
+                &self.funder.to_account_info(),
+                winner_bid.deposit_lamports - price_lamports,
+            )?;
+
+            // creator royalties, creator accounts are passed as remaining accounts
+            let royalties = CreatorRoyalties::fetch(
+                &self.asset.to_account_info(),
+                Some(&self.collection.to_account_info()),
+                creator_accounts,
+                None,
+            )?;
+
+            // pay fee to tresuary, reward to keeper, royalties to creators and funds to owner
+            let settlement = settle_sale(
+                &self.config,
+                &self.vault.to_account_info(),
+                &self.tresuary.to_account_info(),
+                &self.owner.to_account_info(),
+                Some(&self.payer.to_account_info()),
+                &royalties,
+                price_lamports,
+            )?;
+
+            // record sale in the collection stats
+            self.collection_stats.record(self.asset.key(), price_lamports, unix_timestamp);
+
+            // thaw asset and transfer it to winner
+            AssetCustody {
+                core_program: &self.core_program.to_account_info(),
+                asset: &self.asset.to_account_info(),
+                collection: Some(&self.collection.to_account_info()),
+                payer: &self.payer.to_account_info(),
+                system_program: &self.system_program.to_account_info(),
+                authority: &self.asset_auction.to_account_info(),
+            }.release(&self.buyer.to_account_info(), &modify_asset_signer_seeds)?;
+
+            // winner bid account is closed on exit
+            self.asset_auction.close_sealed_bid();
+
+            SealedSettlement::Sold(AuctionCompleted::new(
+                self.config.key(),
+                &self.asset_auction,
+                self.buyer.key(),
+                None,
+                price_lamports,
+                &settlement,
+            )?)
+        } else {
+            // no valid bid, asset goes back to owner
+            AssetCustody {
+                core_program: &self.core_program.to_account_info(),
+                asset: &self.asset.to_account_info(),
+                collection: Some(&self.collection.to_account_info()),
+                payer: &self.payer.to_account_info(),
+                system_program: &self.system_program.to_account_info(),
+                authority: &self.asset_auction.to_account_info(),
+            }.return_to_owner(&modify_asset_signer_seeds)?;
+
+            SealedSettlement::Unsold(AuctionCancelled::new(self.config.key(), &self.asset_auction)?)
+        };
+
+        // remaining bids are refunded with refund_sealed_bid, last one closes the auction
+        if self.asset_auction.settle_sealed() == 0 {
+            self.asset_auction.close(self.owner.to_account_info())?;
+            self.collection_auction.close_auction()?;
+        }
+
+        Ok(settlement)
+    }
+}
\ No newline at end of file
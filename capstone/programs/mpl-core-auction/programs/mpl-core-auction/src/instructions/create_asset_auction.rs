@@ -1,15 +1,13 @@
 use anchor_lang::prelude::*;
 use mpl_core::{
     accounts::{BaseAssetV1, BaseCollectionV1}, 
-    fetch_plugin, 
-    instructions::{AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder, UpdatePluginV1CpiBuilder}, 
-    types::{FreezeDelegate, Plugin, PluginAuthority, PluginType, TransferDelegate, UpdateAuthority},
     ID as CORE_PROGRAM_ID
 };
 
-use crate::{CollectionAuction, Config, AssetAuction, AuctionErrors};
+use crate::{CollectionAuction, Config, AssetAuction, BidHistory, AuctionKind, AuctionSchedule, AuctionErrors, AssetCustody, AuctionCreated};
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateAssetAuction<'info> {
     // EXTERNAL ACCOUNTS
@@ -17,14 +15,17 @@ pub struct CreateAssetAuction<'info> {
     pub payer: Signer<'info>,
     #[account(mut)]
     pub owner: Signer<'info>,
+    // none for standalone assets of an update authority whitelist
     #[account(mut)]
-    pub collection: Account<'info, BaseCollectionV1>,
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
     #[account(
         mut,
         has_one = owner,
-        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()),
+        constraint = asset.update_authority == collection_auction.asset_update_authority(),
     )]
     pub asset: Account<'info, BaseAssetV1>,
+    /// CHECK: No signature or program ownership needed, frontend that brought the listing
+    pub listing_referrer: Option<UncheckedAccount<'info>>,
 
     // INTERNAL ACCOUNTS   
     #[account(
@@ -33,7 +34,8 @@ pub struct CreateAssetAuction<'info> {
     )]
     pub config: Account<'info, Config>,
     #[account(
-        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
         bump = collection_auction.bump,
     )]
     pub collection_auction: Account<'info, CollectionAuction>,
@@ -45,6 +47,14 @@ pub struct CreateAssetAuction<'info> {
         space = 8 + AssetAuction::INIT_SPACE
     )]
     pub asset_auction: Account<'info, AssetAuction>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"bid_history", asset_auction.key().as_ref()],
+        bump,
+        space = 8 + BidHistory::INIT_SPACE,
+    )]
+    pub bid_history: Box<Account<'info, BidHistory>>,
 
     // PROGRAMS
     pub system_program: Program<'info, System>,
@@ -54,108 +64,94 @@ pub struct CreateAssetAuction<'info> {
 }
 
 impl<'info> CreateAssetAuction<'info> {
-    pub fn create_asset_auction(&mut self, duration_minutes: u32, min_bid_lamports: u64, bumps: &CreateAssetAuctionBumps) -> Result<()> {
+    pub fn create_asset_auction(&mut self, duration_minutes: u32, min_bid_lamports: u64, buy_now_lamports: Option<u64>, buy_now_cutoff_lamports: Option<u64>, schedule: Option<AuctionSchedule>, bumps: &CreateAssetAuctionBumps) -> Result<AuctionCreated> {
+        // paused program or collections do not accept new auctions
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        let collection = self.collection.as_ref().map(|collection| collection.to_account_info());
+        self.collection_auction.check_collection(collection.as_ref())?;
+
+        // scheduled auctions run between fixed timestamps, duration derives from them
+        let (duration_minutes, start_timestamp, end_timestamp) = match schedule {
+            None => (duration_minutes, 0, 0),
+            Some(AuctionSchedule { start_ts, end_ts }) => {
+                let unix_timestamp = Clock::get()?.unix_timestamp;
+                require!(start_ts > 0 && end_ts > start_ts && end_ts > unix_timestamp, AuctionErrors::InvalidSchedule);
+                let duration_minutes = u32::try_from((end_ts - start_ts) / 60).map_err(|_| AuctionErrors::DurationTooLong)?;
+                (duration_minutes, start_ts, end_ts)
+            }
+        };
+
         // duration validations
         require!(duration_minutes >= self.config.min_duration_minutes, AuctionErrors::DurationTooShort);
         require!(duration_minutes <= self.config.max_duration_minutes, AuctionErrors::DurationTooLong);
 
+        // buy now validations
+        if let Some(buy_now_lamports) = buy_now_lamports {
+            require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+            require!(buy_now_lamports > min_bid_lamports, AuctionErrors::InvalidBuyNowPrice);
+        }
+        if let Some(cutoff_lamports) = buy_now_cutoff_lamports {
+            require!(buy_now_lamports.is_some_and(|buy_now| cutoff_lamports <= buy_now), AuctionErrors::InvalidBuyNowPrice);
+        }
+
+        // sellers can not refer their own listing, referrer shares are only paid in lamports
+        if let Some(listing_referrer) = &self.listing_referrer {
+            require!(listing_referrer.key() != self.owner.key(), AuctionErrors::InvalidReferrer);
+            require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+        }
+
+        // track open auctions, the collection can only be removed once all of them are closed
+        self.collection_auction.open_auctions += 1;
+
         // create data account
         self.asset_auction.set_inner(
             AssetAuction {
-                collection: self.collection.key(),
+                collection: self.collection_auction.collection,
                 core_asset: self.asset.key(),
                 owner: self.owner.key(),
+                kind: AuctionKind::English,
                 duration_minutes,
                 min_bid_lamports,
                 buyer: self.owner.key(), // owner can not bid, it starts as incial buyer instead of using None
+                funder: self.owner.key(),
                 buyer_bid_lamports: 0,
                 first_bid_timestamp: 0,
+                start_timestamp,
+                end_timestamp,
+                extensions: 0,
+                buy_now_lamports,
+                buy_now_cutoff_lamports,
+                allowlist_root: None,
+                listing_referrer: self.listing_referrer.as_ref().map(|listing_referrer| listing_referrer.key()),
+                bid_referrer: None,
                 bump: bumps.asset_auction,
             }
         );
+        self.bid_history.set_inner(BidHistory {
+            asset_auction: self.asset_auction.key(),
+            total_bids: 0,
+            bids: Vec::new(),
+            bump: bumps.bid_history,
+        });
 
-        // check if freeze delegate plugin exists
-        match fetch_plugin::<BaseAssetV1, FreezeDelegate>(&self.asset.to_account_info(), PluginType::FreezeDelegate) {
-            Ok((plugin_authority, freeze_delegate, _)) => {
-                // check if asset is frozen or has an active freeze delegate
-                require!(freeze_delegate.frozen == false, AuctionErrors::FrozenAsset);
-                require!(plugin_authority == PluginAuthority::Owner, AuctionErrors::FreezeDelegateNotOwner);
-
-                // update freeze delegate authority
-                ApprovePluginAuthorityV1CpiBuilder::new(&self.core_program.to_account_info())
-                    .asset(&self.asset.to_account_info())
-                    .collection(Some(&self.collection.to_account_info()))
-                    .payer(&self.payer.to_account_info())
-                    .authority(Some(&self.owner.to_account_info()))
-                    .system_program(&self.system_program.to_account_info())
-                    .plugin_type(PluginType::FreezeDelegate)
-                    .new_authority(PluginAuthority::Address { address: self.asset_auction.key() })
-                    .invoke()?;
-
-                // freeze with asset auction pda seeds signature
-                let signer_seeds: [&[&[u8]]; 1] = [&[
-                    self.collection_auction.to_account_info().key.as_ref(),
-                    self.asset.to_account_info().key.as_ref(),
-                    &[self.asset_auction.bump],
-                ]];
-
-                UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
-                    .asset(&self.asset.to_account_info())
-                    .collection(Some(&self.collection.to_account_info()))
-                    .payer(&self.payer.to_account_info())
-                    .authority(Some(&self.asset_auction.to_account_info()))
-                    .system_program(&self.system_program.to_account_info())
-                    .plugin(Plugin::FreezeDelegate( FreezeDelegate { frozen: true } ))
-                    .invoke_signed(&signer_seeds)?;
-                
-            }
-            Err(_) => {
-                // Freeze the asset
-                AddPluginV1CpiBuilder::new(&self.core_program.to_account_info())
-                    .asset(&self.asset.to_account_info())
-                    .collection(Some(&self.collection.to_account_info()))
-                    .payer(&self.payer.to_account_info())
-                    .authority(Some(&self.owner.to_account_info()))
-                    .system_program(&self.system_program.to_account_info())
-                    .plugin(Plugin::FreezeDelegate( FreezeDelegate { frozen: true } ))
-                    .init_authority(PluginAuthority::Address { address: self.asset_auction.key() })
-                    .invoke()?;
-            
-            }
-        };
-    
-        // check if transfer delegate plugin exists
-        match fetch_plugin::<BaseAssetV1, TransferDelegate>(&self.asset.to_account_info(), PluginType::TransferDelegate) {
-            Ok((plugin_authority, _, _)) => {
-                // check if asset has active transfer delegate
-                require!(plugin_authority == PluginAuthority::Owner, AuctionErrors::TransferDelegateNotOwner);
-
-                // update transfer delegate authority
-                ApprovePluginAuthorityV1CpiBuilder::new(&self.core_program.to_account_info())
-                    .asset(&self.asset.to_account_info())
-                    .collection(Some(&self.collection.to_account_info()))
-                    .payer(&self.payer.to_account_info())
-                    .authority(Some(&self.owner.to_account_info()))
-                    .system_program(&self.system_program.to_account_info())
-                    .plugin_type(PluginType::TransferDelegate)
-                    .new_authority(PluginAuthority::Address { address: self.asset_auction.key() })
-                    .invoke()?;
-            }
-            Err(_) => {
-                // Add transfer delegate
-                AddPluginV1CpiBuilder::new(&self.core_program.to_account_info())
-                    .asset(&self.asset.to_account_info())
-                    .collection(Some(&self.collection.to_account_info()))
-                    .payer(&self.payer.to_account_info())
-                    .authority(Some(&self.owner.to_account_info()))
-                    .system_program(&self.system_program.to_account_info())
-                    .plugin(Plugin::TransferDelegate( TransferDelegate { } ))
-                    .init_authority(PluginAuthority::Address { address: self.asset_auction.key() })
-                    .invoke()?;
-            }
-        };
+        // freeze asset and delegate custody to the asset auction
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            self.collection_auction.to_account_info().key.as_ref(),
+            self.asset.to_account_info().key.as_ref(),
+            &[self.asset_auction.bump],
+        ]];
+
+        AssetCustody {
+            core_program: &self.core_program.to_account_info(),
+            asset: &self.asset.to_account_info(),
+            collection: collection.as_ref(),
+            payer: &self.payer.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            authority: &self.asset_auction.to_account_info(),
+        }.lock(&self.owner.to_account_info(), &signer_seeds)?;
 
-        Ok(())
+        Ok(AuctionCreated::new(self.config.key(), &self.asset_auction))
     }
 
 }
\ No newline at end of file
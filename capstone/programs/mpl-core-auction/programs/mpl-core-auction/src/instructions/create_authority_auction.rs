@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{CollectionAuction, CollectionStats, Config, ExtensionParams, WhitelistKind, AuctionErrors, CollectionWhitelisted};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateAuthorityAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: No signature or program ownership needed, update authority address of standalone assets
+    pub update_authority: UncheckedAccount<'info>,
+
+    // INTERNAL ACCOUNTS   
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"collection", config.key().as_ref(), update_authority.key().as_ref()],
+        bump,
+        space = 8 + CollectionAuction::INIT_SPACE,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump,
+        space = 8 + CollectionStats::INIT_SPACE,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> CreateAuthorityAuction<'info> {
+    pub fn create_authority_auction(&mut self, extension: Option<ExtensionParams>, bumps: &CreateAuthorityAuctionBumps) -> Result<CollectionWhitelisted> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+        if let Some(extension) = extension {
+            require!(extension.is_valid(), AuctionErrors::InvalidExtension);
+        }
+
+        // standalone assets whose update authority is this address, bids are paid in lamports
+        self.collection_auction.set_inner( CollectionAuction {
+            collection: self.update_authority.key(),
+            kind: WhitelistKind::UpdateAuthority,
+            extension,
+            quote_mint: None,
+            enabled: true,
+            open_auctions: 0,
+            gate: None,
+            bump: bumps.collection_auction,
+        });
+        self.collection_stats.set_inner(CollectionStats::new(self.collection_auction.key(), bumps.collection_stats));
+        Ok(CollectionWhitelisted {
+            config: self.config.key(),
+            collection: self.update_authority.key(),
+            collection_auction: self.collection_auction.key(),
+            quote_mint: None,
+        })
+    }
+}
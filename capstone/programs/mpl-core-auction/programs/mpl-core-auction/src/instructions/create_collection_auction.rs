@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
 use mpl_core::{ID as CORE_PROGRAM_ID, accounts::BaseCollectionV1};
 
-use crate::{CollectionAuction, Config, AuctionErrors};
+use crate::{CollectionAuction, CollectionStats, Config, ExtensionParams, WhitelistKind, AuctionErrors, CollectionWhitelisted};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateCollectionAuction<'info> {
     // EXTERNAL ACCOUNTS
@@ -11,6 +13,8 @@ pub struct CreateCollectionAuction<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
     pub collection: Account<'info, BaseCollectionV1>,
+    // bids are paid in lamports when no quote mint is given
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
 
     // INTERNAL ACCOUNTS   
     #[account(
@@ -18,6 +22,19 @@ pub struct CreateCollectionAuction<'info> {
         bump = config.bump,
     )]
     pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init,
         payer = payer,
@@ -26,9 +43,19 @@ pub struct CreateCollectionAuction<'info> {
         space = 8 + CollectionAuction::INIT_SPACE,
     )]
     pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump,
+        space = 8 + CollectionStats::INIT_SPACE,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
 
     // PROGRAMS
     pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
     /// CHECK: this will be checked by core
     #[account(address = CORE_PROGRAM_ID)]
     pub core_program: UncheckedAccount<'info>,
@@ -36,12 +63,30 @@ pub struct CreateCollectionAuction<'info> {
 
 
 impl<'info> CreateCollectionAuction<'info> {
-    pub fn create_collection_auction(&mut self, bumps: &CreateCollectionAuctionBumps) -> Result<()> {
+    pub fn create_collection_auction(&mut self, extension: Option<ExtensionParams>, bumps: &CreateCollectionAuctionBumps) -> Result<CollectionWhitelisted> {
         require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+        if let Some(extension) = extension {
+            require!(extension.is_valid(), AuctionErrors::InvalidExtension);
+        }
+        if self.quote_mint.is_some() {
+            require!(self.vault_token_account.is_some(), AuctionErrors::MissingTokenAccounts);
+        }
         self.collection_auction.set_inner( CollectionAuction {
             collection: self.collection.key(),
+            kind: WhitelistKind::Collection,
+            extension,
+            quote_mint: self.quote_mint.as_ref().map(|quote_mint| quote_mint.key()),
+            enabled: true,
+            open_auctions: 0,
+            gate: None,
             bump: bumps.collection_auction,
         });
-        Ok(())
+        self.collection_stats.set_inner(CollectionStats::new(self.collection_auction.key(), bumps.collection_stats));
+        Ok(CollectionWhitelisted {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            collection_auction: self.collection_auction.key(),
+            quote_mint: self.collection_auction.quote_mint,
+        })
     }
 }
\ No newline at end of file
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    types::UpdateAuthority,
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{CollectionAuction, Config, LotAuction, AuctionErrors, AssetCustody, LotCreated, MAX_LOT_ASSETS, same_royalties};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(seed: u32)]
+pub struct CreateLotAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lot", collection_auction.key().as_ref(), owner.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + LotAuction::INIT_SPACE,
+    )]
+    pub lot_auction: Account<'info, LotAuction>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+
+impl<'info> CreateLotAuction<'info> {
+    pub fn create_lot_auction(&mut self, seed: u32, duration_minutes: u32, min_bid_lamports: u64, asset_accounts: &[AccountInfo<'info>], bumps: &CreateLotAuctionBumps) -> Result<LotCreated> {
+        // paused program or collections do not accept new auctions
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+
+        // duration validations
+        require!(duration_minutes >= self.config.min_duration_minutes, AuctionErrors::DurationTooShort);
+        require!(duration_minutes <= self.config.max_duration_minutes, AuctionErrors::DurationTooLong);
+
+        // assets are passed as remaining accounts, owned by the owner and part of the collection
+        require!(!asset_accounts.is_empty() && asset_accounts.len() <= MAX_LOT_ASSETS, AuctionErrors::InvalidLotAssets);
+        let mut assets: Vec<Pubkey> = Vec::with_capacity(asset_accounts.len());
+        for asset_account in asset_accounts {
+            let asset = Account::<BaseAssetV1>::try_from(asset_account)?;
+            require!(asset.owner == self.owner.key(), AuctionErrors::InvalidLotAssets);
+            require!(asset.update_authority == UpdateAuthority::Collection(self.collection.key()), AuctionErrors::InvalidLotAssets);
+            require!(!assets.contains(&asset_account.key()), AuctionErrors::InvalidLotAssets);
+            assets.push(asset_account.key());
+        }
+
+        // the lot is settled with a single royalties plugin, every asset must resolve to it
+        require!(same_royalties(asset_accounts, Some(&self.collection.to_account_info())), AuctionErrors::LotRoyaltiesMismatch);
+
+        // track open auctions, the collection can only be removed once all of them are closed
+        self.collection_auction.open_auctions += 1;
+
+        // create data account
+        self.lot_auction.set_inner(LotAuction {
+            collection: self.collection.key(),
+            owner: self.owner.key(),
+            seed,
+            assets,
+            duration_minutes,
+            min_bid_lamports,
+            buyer: self.owner.key(),
+            funder: self.owner.key(),
+            buyer_bid_lamports: 0,
+            first_bid_timestamp: 0,
+            end_timestamp: 0,
+            extensions: 0,
+            bump: bumps.lot_auction,
+        });
+
+        // freeze every asset and delegate custody to the lot auction
+        let collection_auction_key = self.collection_auction.key();
+        let owner_key = self.owner.key();
+        let seed_bytes = seed.to_le_bytes();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"lot",
+            collection_auction_key.as_ref(),
+            owner_key.as_ref(),
+            seed_bytes.as_ref(),
+            &[self.lot_auction.bump],
+        ]];
+
+        for asset_account in asset_accounts {
+            AssetCustody {
+                core_program: &self.core_program.to_account_info(),
+                asset: asset_account,
+                collection: Some(&self.collection.to_account_info()),
+                payer: &self.payer.to_account_info(),
+                system_program: &self.system_program.to_account_info(),
+                authority: &self.lot_auction.to_account_info(),
+            }.lock(&self.owner.to_account_info(), &signer_seeds)?;
+        }
+
+        Ok(LotCreated {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            lot_auction: self.lot_auction.key(),
+            owner: self.owner.key(),
+            assets: self.lot_auction.assets.clone(),
+            min_bid_lamports,
+        })
+    }
+}
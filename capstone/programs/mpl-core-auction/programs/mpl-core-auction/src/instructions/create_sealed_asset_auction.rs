@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1}, 
+    types::UpdateAuthority,
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{CollectionAuction, Config, AssetAuction, AuctionKind, AuctionErrors, AssetCustody, AuctionCreated};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateSealedAssetAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()),
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS   
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [collection_auction.key().as_ref(), asset.key().as_ref()],
+        bump,
+        space = 8 + AssetAuction::INIT_SPACE
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> CreateSealedAssetAuction<'info> {
+    pub fn create_sealed_asset_auction(&mut self, commit_minutes: u32, reveal_minutes: u32, min_bid_lamports: u64, unrevealed_penalty_bps: u16, bumps: &CreateSealedAssetAuctionBumps) -> Result<AuctionCreated> {
+        // paused program or collections do not accept new auctions
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+
+        // quote mint collections are english only
+        require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+
+        // duration validations
+        require!(commit_minutes >= self.config.min_duration_minutes, AuctionErrors::DurationTooShort);
+        require!(commit_minutes <= self.config.max_duration_minutes, AuctionErrors::DurationTooLong);
+        require!(reveal_minutes > 0, AuctionErrors::InvalidRevealDuration);
+        require!(unrevealed_penalty_bps <= 10_000, AuctionErrors::InvalidPenalty);
+
+        // commit phase starts at creation
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        let commit_end_timestamp = unix_timestamp + commit_minutes as i64 * 60;
+
+        // track open auctions, the collection can only be removed once all of them are closed
+        self.collection_auction.open_auctions += 1;
+
+        // create data account
+        self.asset_auction.set_inner(
+            AssetAuction {
+                collection: self.collection.key(),
+                core_asset: self.asset.key(),
+                owner: self.owner.key(),
+                kind: AuctionKind::Sealed {
+                    commit_end_timestamp,
+                    unrevealed_penalty_bps,
+                    second_bid_lamports: 0,
+                    open_bids: 0,
+                    settled: false,
+                },
+                duration_minutes: commit_minutes,
+                min_bid_lamports,
+                buyer: self.owner.key(), // highest revealed bidder, owner until a valid reveal
+                funder: self.owner.key(),
+                buyer_bid_lamports: 0,
+                first_bid_timestamp: 0,
+                start_timestamp: 0,
+                end_timestamp: commit_end_timestamp + reveal_minutes as i64 * 60,
+                extensions: 0,
+                buy_now_lamports: None,
+                buy_now_cutoff_lamports: None,
+                allowlist_root: None,
+                listing_referrer: None,
+                bid_referrer: None,
+                bump: bumps.asset_auction,
+            }
+        );
+
+        // freeze asset and delegate custody to the asset auction
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            self.collection_auction.to_account_info().key.as_ref(),
+            self.asset.to_account_info().key.as_ref(),
+            &[self.asset_auction.bump],
+        ]];
+
+        AssetCustody {
+            core_program: &self.core_program.to_account_info(),
+            asset: &self.asset.to_account_info(),
+            collection: Some(&self.collection.to_account_info()),
+            payer: &self.payer.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            authority: &self.asset_auction.to_account_info(),
+        }.lock(&self.owner.to_account_info(), &signer_seeds)?;
+
+        Ok(AuctionCreated::new(self.config.key(), &self.asset_auction))
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{AssetAuction, BidHistory, AuctionErrors, AuctionKind, CollectionAuction, Config, AssetCustody, TokenVault, AuctionCancelled, transfer_from_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmergencyRefundAssetAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = asset_auction.funder,
+    )]
+    pub funder: UncheckedAccount<'info>,
+    // none for standalone assets of an update authority whitelist
+    #[account(mut)]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [collection_auction.key().as_ref(), asset.key().as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+    // english auctions only, other kinds have no bid history
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"bid_history", asset_auction.key().as_ref()],
+        bump = bid_history.bump,
+    )]
+    pub bid_history: Option<Box<Account<'info, BidHistory>>>,
+
+    // QUOTE MINT ACCOUNTS, only for collections bidding in a quote mint
+    #[account(
+        constraint = collection_auction.quote_mint == Some(quote_mint.key()) @ AuctionErrors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = funder,
+        associated_token::token_program = token_program,
+    )]
+    pub funder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> EmergencyRefundAssetAuction<'info> {
+    pub fn emergency_refund_asset_auction(&mut self) -> Result<AuctionCancelled> {
+        // only available while the program is paused
+        require!(self.config.paused, AuctionErrors::ProgramNotPaused);
+
+        // sealed deposits are refunded per bid, english and dutch auctions hold at most one bid
+        require!(!matches!(self.asset_auction.kind, AuctionKind::Sealed { .. }), AuctionErrors::InvalidAuctionKind);
+        require!(self.asset_auction.kind != AuctionKind::English || self.bid_history.is_some(), AuctionErrors::MissingBidHistory);
+        let collection = self.collection.as_ref().map(|collection| collection.to_account_info());
+        self.collection_auction.check_collection(collection.as_ref())?;
+
+        // refund current high bid to its funder
+        if self.asset_auction.first_bid_timestamp != 0 {
+            let buyer_bid_lamports = self.asset_auction.buyer_bid_lamports;
+            match self.collection_auction.quote_mint {
+                None => transfer_from_vault(
+                    &self.config,
+                    &self.vault.to_account_info(),
+                    &self.funder.to_account_info(),
+                    buyer_bid_lamports,
+                )?,
+                Some(_) => match (&self.quote_mint, &self.vault_token_account, &self.funder_token_account, &self.token_program) {
+                    (Some(mint), Some(vault_token_account), Some(funder_token_account), Some(token_program)) => TokenVault {
+                        config: &self.config,
+                        vault: &self.vault,
+                        mint,
+                        vault_token_account,
+                        token_program,
+                    }.withdraw(funder_token_account, buyer_bid_lamports)?,
+                    _ => return err!(AuctionErrors::MissingTokenAccounts),
+                },
+            };
+        }
+
+        // asset auction is closed, collection open auctions counter goes down
+        self.collection_auction.close_auction()?;
+
+        // modify asset signer seeds
+        let modify_asset_signer_seeds: [&[&[u8]]; 1] = [&[
+            self.collection_auction.to_account_info().key.as_ref(),
+            self.asset.to_account_info().key.as_ref(),
+            &[self.asset_auction.bump],
+        ]];
+
+        // thaw asset and hand it back to owner
+        AssetCustody {
+            core_program: &self.core_program.to_account_info(),
+            asset: &self.asset.to_account_info(),
+            collection: collection.as_ref(),
+            payer: &self.payer.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            authority: &self.asset_auction.to_account_info(),
+        }.return_to_owner(&modify_asset_signer_seeds)?;
+
+        AuctionCancelled::new(self.config.key(), &self.asset_auction)
+    }
+}
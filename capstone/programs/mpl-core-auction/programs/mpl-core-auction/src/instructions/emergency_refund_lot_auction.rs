@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseCollectionV1,
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{CollectionAuction, Config, LotAuction, AuctionErrors, AssetCustody, LotCancelled, transfer_from_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmergencyRefundLotAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = lot_auction.funder,
+    )]
+    pub funder: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        has_one = collection,
+        seeds = [b"lot", collection_auction.key().as_ref(), owner.key().as_ref(), lot_auction.seed.to_le_bytes().as_ref()],
+        bump = lot_auction.bump,
+    )]
+    pub lot_auction: Account<'info, LotAuction>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+
+impl<'info> EmergencyRefundLotAuction<'info> {
+    // remaining accounts are the lot assets in order
+    pub fn emergency_refund_lot_auction(&mut self, asset_accounts: &[AccountInfo<'info>]) -> Result<LotCancelled> {
+        // only available while the program is paused
+        require!(self.config.paused, AuctionErrors::ProgramNotPaused);
+        require!(self.lot_auction.check_assets(asset_accounts), AuctionErrors::InvalidLotAssets);
+
+        // refund current high bid to its funder, lots bid in sol only
+        if self.lot_auction.first_bid_timestamp != 0 {
+            transfer_from_vault(
+                &self.config,
+                &self.vault.to_account_info(),
+                &self.funder.to_account_info(),
+                self.lot_auction.buyer_bid_lamports,
+            )?;
+        }
+
+        // lot auction is closed, collection open auctions counter goes down
+        self.collection_auction.close_auction()?;
+
+        // set program signer seeds
+        let collection_auction_key = self.collection_auction.key();
+        let owner_key = self.owner.key();
+        let seed_bytes = self.lot_auction.seed.to_le_bytes();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"lot",
+            collection_auction_key.as_ref(),
+            owner_key.as_ref(),
+            seed_bytes.as_ref(),
+            &[self.lot_auction.bump],
+        ]];
+
+        // thaw every asset and hand it back to owner
+        for asset_account in asset_accounts {
+            AssetCustody {
+                core_program: &self.core_program.to_account_info(),
+                asset: asset_account,
+                collection: Some(&self.collection.to_account_info()),
+                payer: &self.payer.to_account_info(),
+                system_program: &self.system_program.to_account_info(),
+                authority: &self.lot_auction.to_account_info(),
+            }.return_to_owner(&signer_seeds)?;
+        }
+
+        Ok(LotCancelled {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            lot_auction: self.lot_auction.key(),
+            owner: self.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    ID as CORE_PROGRAM_ID
+};
+
+use crate::{AssetAuction, BidHistory, AuctionErrors, AuctionKind, CollectionAuction, Config, AssetCustody, AuctionCancelled};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireAssetAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    // none for standalone assets of an update authority whitelist
+    #[account(mut)]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [collection_auction.key().as_ref(), asset.key().as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"bid_history", asset_auction.key().as_ref()],
+        bump = bid_history.bump,
+    )]
+    pub bid_history: Box<Account<'info, BidHistory>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    /// CHECK: this will be checked by core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> ExpireAssetAuction<'info> {
+    pub fn expire_asset_auction(&mut self) -> Result<AuctionCancelled> {
+        // validate auction kind, only scheduled auctions expire
+        require!(self.asset_auction.kind == AuctionKind::English, AuctionErrors::InvalidAuctionKind);
+        require!(self.asset_auction.is_scheduled(), AuctionErrors::AuctionNotScheduled);
+
+        // validate auction is over
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        require!(unix_timestamp >= self.asset_auction.end_timestamp, AuctionErrors::AuctionRunning);
+
+        // auctions with a bid are settled with complete_asset_auction
+        require!(self.asset_auction.first_bid_timestamp == 0, AuctionErrors::AuctionStarted);
+        let collection = self.collection.as_ref().map(|collection| collection.to_account_info());
+        self.collection_auction.check_collection(collection.as_ref())?;
+
+        // asset auction is closed, collection open auctions counter goes down
+        self.collection_auction.close_auction()?;
+
+        // modify asset signer seeds
+        let modify_asset_signer_seeds: [&[&[u8]]; 1] = [&[
+            self.collection_auction.to_account_info().key.as_ref(),
+            self.asset.to_account_info().key.as_ref(),
+            &[self.asset_auction.bump],
+        ]];
+
+        // thaw asset and hand it back to owner, no owner signature needed
+        AssetCustody {
+            core_program: &self.core_program.to_account_info(),
+            asset: &self.asset.to_account_info(),
+            collection: collection.as_ref(),
+            payer: &self.payer.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            authority: &self.asset_auction.to_account_info(),
+        }.return_to_owner(&modify_asset_signer_seeds)?;
+
+        AuctionCancelled::new(self.config.key(), &self.asset_auction)
+    }
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::{Config, AuctionErrors};
+use crate::{Config, MAX_FEE_BPS, ExtensionParams, BidIncrement, AuctionErrors, ConfigInitialized};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(seed: u32)]
 pub struct Initialize<'info> {
@@ -37,18 +38,34 @@ pub struct Initialize<'info> {
 
 
 impl<'info> Initialize<'info> {
-    pub fn initialize(&mut self, seed: u32, fee_bps: u8, min_duration_minutes: u32, max_duration_minutes: u32, bumps: &InitializeBumps) -> Result<()> {
+    pub fn initialize(&mut self, seed: u32, fee_bps: u16, min_duration_minutes: u32, max_duration_minutes: u32, extension: ExtensionParams, min_bid_increment: BidIncrement, bumps: &InitializeBumps) -> Result<ConfigInitialized> {
+        require!(fee_bps <= MAX_FEE_BPS, AuctionErrors::InvalidFee);
         require!(max_duration_minutes > min_duration_minutes, AuctionErrors::InvalidMinMaxDuration);
+        require!(extension.is_valid(), AuctionErrors::InvalidExtension);
         self.config.set_inner(Config{
             seed,
             admin: self.admin.key(),
+            pending_admin: None,
+            pauser: self.admin.key(),
+            paused: false,
             fee_bps,
             min_duration_minutes,
             max_duration_minutes,
+            extension,
+            min_bid_increment,
+            keeper_reward: None,
+            referrer_fee: None,
             vault_bump: bumps.vault,
             tresuary_bump: bumps.tresuary,
             bump: bumps.config,
         });
-        Ok(())
+        Ok(ConfigInitialized {
+            config: self.config.key(),
+            admin: self.admin.key(),
+            seed,
+            fee_bps,
+            min_duration_minutes,
+            max_duration_minutes,
+        })
     }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use mpl_core::accounts::BaseCollectionV1;
+
+use crate::{CollectionAuction, CollectionOffer, Config, AuctionErrors, CollectionOfferMade, transfer_to_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MakeCollectionOffer<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub collection: Account<'info, BaseCollectionV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"collection_offer", config.key().as_ref(), collection.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = 8 + CollectionOffer::INIT_SPACE,
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> MakeCollectionOffer<'info> {
+    pub fn make_collection_offer(&mut self, lamports: u64, quantity: u32, expiry_timestamp: i64, bumps: &MakeCollectionOfferBumps) -> Result<CollectionOfferMade> {
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        require!(lamports > 0 && quantity > 0 && expiry_timestamp > unix_timestamp, AuctionErrors::InvalidOffer);
+
+        // every unit is escrowed in the vault
+        let total_lamports = lamports.checked_mul(quantity as u64).ok_or(AuctionErrors::InvalidOffer)?;
+        transfer_to_vault(&self.bidder.to_account_info(), &self.vault.to_account_info(), total_lamports)?;
+
+        self.collection_offer.set_inner(CollectionOffer {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            bidder: self.bidder.key(),
+            lamports,
+            quantity,
+            filled: 0,
+            expiry_timestamp,
+            bump: bumps.collection_offer,
+        });
+
+        Ok(CollectionOfferMade {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            collection_offer: self.collection_offer.key(),
+            bidder: self.bidder.key(),
+            lamports,
+            quantity,
+            expiry_timestamp,
+        })
+    }
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    types::UpdateAuthority,
+};
+
+use crate::{CollectionAuction, Config, Offer, AuctionErrors, OfferMade, transfer_to_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MakeOffer<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub collection: Account<'info, BaseCollectionV1>,
+    #[account(
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()),
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"offer", config.key().as_ref(), asset.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = 8 + Offer::INIT_SPACE,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> MakeOffer<'info> {
+    pub fn make_offer(&mut self, lamports: u64, expiry_timestamp: i64, bumps: &MakeOfferBumps) -> Result<OfferMade> {
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+        require!(self.bidder.key() != self.asset.owner, AuctionErrors::OwnerBid);
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        require!(lamports > 0 && expiry_timestamp > unix_timestamp, AuctionErrors::InvalidOffer);
+
+        // offered lamports are escrowed in the vault
+        transfer_to_vault(&self.bidder.to_account_info(), &self.vault.to_account_info(), lamports)?;
+
+        self.offer.set_inner(Offer {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            asset: self.asset.key(),
+            bidder: self.bidder.key(),
+            lamports,
+            expiry_timestamp,
+            bump: bumps.offer,
+        });
+
+        Ok(OfferMade {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            asset: self.asset.key(),
+            offer: self.offer.key(),
+            bidder: self.bidder.key(),
+            lamports,
+            expiry_timestamp,
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use mpl_core::accounts::BaseCollectionV1;
+
+use crate::{CollectionAuction, Config, Trait, TraitOffer, AuctionErrors, TraitOfferMade, MAX_TRAITS, transfer_to_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(seed: u32)]
+pub struct MakeTraitOffer<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub collection: Account<'info, BaseCollectionV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"trait_offer", collection_auction.key().as_ref(), bidder.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + TraitOffer::INIT_SPACE,
+    )]
+    pub trait_offer: Account<'info, TraitOffer>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> MakeTraitOffer<'info> {
+    pub fn make_trait_offer(&mut self, seed: u32, lamports: u64, traits: Vec<Trait>, expiry_timestamp: i64, bumps: &MakeTraitOfferBumps) -> Result<TraitOfferMade> {
+        require!(!self.config.paused, AuctionErrors::ProgramPaused);
+        require!(self.collection_auction.enabled, AuctionErrors::CollectionPaused);
+        require!(self.collection_auction.quote_mint.is_none(), AuctionErrors::QuoteMintNotSupported);
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        require!(lamports > 0 && expiry_timestamp > unix_timestamp, AuctionErrors::InvalidOffer);
+        require!(!traits.is_empty() && traits.len() <= MAX_TRAITS, AuctionErrors::InvalidTraits);
+        require!(traits.iter().all(|required| required.is_valid()), AuctionErrors::InvalidTraits);
+
+        // offered lamports are escrowed in the vault
+        transfer_to_vault(&self.bidder.to_account_info(), &self.vault.to_account_info(), lamports)?;
+
+        self.trait_offer.set_inner(TraitOffer {
+            config: self.config.key(),
+            collection_auction: self.collection_auction.key(),
+            collection: self.collection.key(),
+            bidder: self.bidder.key(),
+            seed,
+            lamports,
+            expiry_timestamp,
+            traits: traits.clone(),
+            bump: bumps.trait_offer,
+        });
+
+        Ok(TraitOfferMade {
+            config: self.config.key(),
+            collection: self.collection.key(),
+            trait_offer: self.trait_offer.key(),
+            bidder: self.bidder.key(),
+            lamports,
+            traits,
+            expiry_timestamp,
+        })
+    }
+}
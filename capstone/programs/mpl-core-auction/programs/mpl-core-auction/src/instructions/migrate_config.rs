@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{Config, ConfigV0, ConfigV1, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    /// CHECK: legacy layout can not be deserialized as Config, discriminator and seeds are checked below
+    #[account(mut, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> MigrateConfig<'info> {
+    pub fn migrate_config(&mut self) -> Result<()> {
+        let config_info = self.config.to_account_info();
+
+        // read the legacy layout, the account size tells the version
+        let config = {
+            let data = config_info.try_borrow_data()?;
+            require!(data.len() >= 8 && data[..8] == Config::DISCRIMINATOR, ErrorCode::AccountDiscriminatorMismatch);
+            match data.len() - 8 {
+                ConfigV0::INIT_SPACE => ConfigV0::deserialize(&mut &data[8..])?.migrate(),
+                ConfigV1::INIT_SPACE => ConfigV1::deserialize(&mut &data[8..])?.migrate(),
+                _ => return err!(AuctionErrors::ConfigAlreadyMigrated),
+            }
+        };
+        require!(self.admin.key() == config.admin, AuctionErrors::InvalidAdmin);
+
+        // check config pda seeds
+        let config_key = Pubkey::create_program_address(
+            &[b"config", config.seed.to_le_bytes().as_ref(), &[config.bump]],
+            &crate::ID,
+        ).map_err(|_| ErrorCode::ConstraintSeeds)?;
+        require_keys_eq!(config_key, config_info.key(), ErrorCode::ConstraintSeeds);
+
+        // admin pays the rent of the wider layout
+        let space = 8 + Config::INIT_SPACE;
+        let rent_lamports = Rent::get()?.minimum_balance(space).saturating_sub(config_info.lamports());
+        if rent_lamports > 0 {
+            let cpi_accounts = Transfer {
+                from: self.admin.to_account_info(),
+                to: config_info.clone(),
+            };
+            transfer(CpiContext::new(self.system_program.to_account_info(), cpi_accounts), rent_lamports)?;
+        }
+        config_info.realloc(space, false)?;
+
+        // rewrite the config in the current layout
+        let mut data = config_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data[..];
+        config.try_serialize(&mut writer)?;
+        Ok(())
+    }
+}
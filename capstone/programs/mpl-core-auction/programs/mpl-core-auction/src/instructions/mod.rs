@@ -2,12 +2,90 @@ pub mod initialize;
 pub mod create_collection_auction;
 pub mod create_asset_auction;
 pub mod cancel_asset_auction;
+pub mod expire_asset_auction;
 pub mod bid_asset_auction;
 pub mod complete_asset_auction;
+pub mod create_dutch_asset_auction;
+pub mod buy_dutch_asset_auction;
+pub mod create_sealed_asset_auction;
+pub mod commit_sealed_bid;
+pub mod reveal_sealed_bid;
+pub mod complete_sealed_asset_auction;
+pub mod refund_sealed_bid;
+pub mod buy_now_asset_auction;
+pub mod update_config;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod withdraw_treasury;
+pub mod pause_collection;
+pub mod resume_collection;
+pub mod remove_collection_auction;
+pub mod set_pauser;
+pub mod set_paused;
+pub mod emergency_refund_asset_auction;
+pub mod withdraw_refund;
+pub mod make_offer;
+pub mod cancel_offer;
+pub mod accept_offer;
+pub mod make_collection_offer;
+pub mod cancel_collection_offer;
+pub mod fill_collection_offer;
+pub mod make_trait_offer;
+pub mod cancel_trait_offer;
+pub mod accept_trait_offer;
+pub mod create_lot_auction;
+pub mod bid_lot_auction;
+pub mod cancel_lot_auction;
+pub mod complete_lot_auction;
+pub mod emergency_refund_lot_auction;
+pub mod set_auction_allowlist;
+pub mod set_collection_gate;
+pub mod migrate_config;
+pub mod set_referrer_fee;
+pub mod create_authority_auction;
 
 pub use initialize::*;
 pub use create_collection_auction::*;
 pub use create_asset_auction::*;
 pub use cancel_asset_auction::*;
+pub use expire_asset_auction::*;
 pub use bid_asset_auction::*;
 pub use complete_asset_auction::*;
+pub use create_dutch_asset_auction::*;
+pub use buy_dutch_asset_auction::*;
+pub use create_sealed_asset_auction::*;
+pub use commit_sealed_bid::*;
+pub use reveal_sealed_bid::*;
+pub use complete_sealed_asset_auction::*;
+pub use refund_sealed_bid::*;
+pub use buy_now_asset_auction::*;
+pub use update_config::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use withdraw_treasury::*;
+pub use pause_collection::*;
+pub use resume_collection::*;
+pub use remove_collection_auction::*;
+pub use set_pauser::*;
+pub use set_paused::*;
+pub use emergency_refund_asset_auction::*;
+pub use withdraw_refund::*;
+pub use make_offer::*;
+pub use cancel_offer::*;
+pub use accept_offer::*;
+pub use make_collection_offer::*;
+pub use cancel_collection_offer::*;
+pub use fill_collection_offer::*;
+pub use make_trait_offer::*;
+pub use cancel_trait_offer::*;
+pub use accept_trait_offer::*;
+pub use create_lot_auction::*;
+pub use bid_lot_auction::*;
+pub use cancel_lot_auction::*;
+pub use complete_lot_auction::*;
+pub use emergency_refund_lot_auction::*;
+pub use set_auction_allowlist::*;
+pub use set_collection_gate::*;
+pub use migrate_config::*;
+pub use set_referrer_fee::*;
+pub use create_authority_auction::*;
\ No newline at end of file
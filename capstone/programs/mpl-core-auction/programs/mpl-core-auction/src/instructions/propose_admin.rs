@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    // EXTERNAL ACCOUNTS
+    pub admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+
+impl<'info> ProposeAdmin<'info> {
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+
+        // admin role is only transferred once the new admin accepts it
+        self.config.pending_admin = Some(new_admin);
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::{AssetAuction, AuctionErrors, AuctionKind, CollectionAuction, Config, SealedBid, SealedBidRefunded, transfer_from_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundSealedBid<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        address = sealed_bid.bidder,
+    )]
+    pub bidder: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = sealed_bid.funder,
+    )]
+    pub funder: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = asset_auction.owner,
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [collection_auction.key().as_ref(), asset_auction.core_asset.as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+    #[account(
+        mut,
+        close = funder,
+        has_one = asset_auction,
+        seeds = [b"sealed_bid", asset_auction.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump,
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundSealedBid<'info> {
+    pub fn refund_sealed_bid(&mut self) -> Result<SealedBidRefunded> {
+        // refunds are open once the auction is settled
+        let AuctionKind::Sealed { settled, unrevealed_penalty_bps, .. } = self.asset_auction.kind else {
+            return err!(AuctionErrors::InvalidAuctionKind);
+        };
+        require!(settled, AuctionErrors::AuctionNotSettled);
+
+        // unrevealed bids pay a penalty to the owner
+        let deposit_lamports = self.sealed_bid.deposit_lamports;
+        let penalty_lamports = match self.sealed_bid.revealed {
+            true => 0,
+            false => (deposit_lamports as u128 * unrevealed_penalty_bps as u128 / 10_000) as u64,
+        };
+
+        // deposit goes back to its funder
+        let refund_lamports = deposit_lamports - penalty_lamports;
+        transfer_from_vault(
+            &self.config,
+            &self.vault.to_account_info(),
+            &self.funder.to_account_info(),
+            refund_lamports,
+        )?;
+
+        if penalty_lamports > 0 {
+            transfer_from_vault(
+                &self.config,
+                &self.vault.to_account_info(),
+                &self.owner.to_account_info(),
+                penalty_lamports,
+            )?;
+        }
+
+        let event = SealedBidRefunded {
+            config: self.config.key(),
+            collection: self.asset_auction.collection,
+            asset: self.asset_auction.core_asset,
+            asset_auction: self.asset_auction.key(),
+            bidder: self.sealed_bid.bidder,
+            funder: self.sealed_bid.funder,
+            refund_lamports,
+            penalty_lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        };
+
+        // last refunded bid closes the auction
+        if self.asset_auction.close_sealed_bid() == 0 {
+            self.asset_auction.close(self.owner.to_account_info())?;
+            self.collection_auction.close_auction()?;
+        }
+
+        Ok(event)
+    }
+}
\ No newline at end of file
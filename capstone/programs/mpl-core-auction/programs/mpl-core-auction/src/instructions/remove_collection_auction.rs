@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{CollectionAuction, CollectionStats, Config, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct RemoveCollectionAuction<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"stats", collection_auction.key().as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Box<Account<'info, CollectionStats>>,
+}
+
+
+impl<'info> RemoveCollectionAuction<'info> {
+    pub fn remove_collection_auction(&mut self) -> Result<()> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+
+        // asset auctions need the collection auction to be cancelled or completed
+        require!(self.collection_auction.open_auctions == 0, AuctionErrors::CollectionHasOpenAuctions);
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{CollectionAuction, Config, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct ResumeCollection<'info> {
+    // EXTERNAL ACCOUNTS
+    pub admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+}
+
+
+impl<'info> ResumeCollection<'info> {
+    pub fn resume_collection(&mut self) -> Result<()> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+        self.collection_auction.enabled = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use mpl_core::accounts::{BaseAssetV1, BaseCollectionV1};
+
+use crate::{AssetAuction, AuctionErrors, AuctionKind, CollectionAuction, Config, SealedBid, SealedBidRevealed};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealSealedBid<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub collection: Account<'info, BaseCollectionV1>,
+    pub asset: Account<'info, BaseAssetV1>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), collection.key().as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [collection_auction.key().as_ref(), asset.key().as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+    #[account(
+        mut,
+        has_one = asset_auction,
+        seeds = [b"sealed_bid", asset_auction.key().as_ref(), sealed_bid.bidder.as_ref()],
+        bump = sealed_bid.bump,
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+}
+
+impl<'info> RevealSealedBid<'info> {
+    pub fn reveal_sealed_bid(&mut self, lamports: u64, salt: [u8; 32]) -> Result<SealedBidRevealed> {
+        // validate auction kind and reveal phase
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        let AuctionKind::Sealed { commit_end_timestamp, .. } = self.asset_auction.kind else {
+            return err!(AuctionErrors::InvalidAuctionKind);
+        };
+        require!(unix_timestamp >= commit_end_timestamp, AuctionErrors::RevealPhaseNotStarted);
+        require!(unix_timestamp < self.asset_auction.end_timestamp, AuctionErrors::RevealPhaseOver);
+
+        // validate bid against commitment and deposit
+        require!(!self.sealed_bid.revealed, AuctionErrors::AlreadyRevealed);
+        let commitment = hashv(&[&lamports.to_le_bytes(), &salt]).to_bytes();
+        require!(commitment == self.sealed_bid.commitment, AuctionErrors::InvalidReveal);
+        require!(lamports <= self.sealed_bid.deposit_lamports, AuctionErrors::DepositTooLow);
+
+        // store revealed bid and rank it
+        self.sealed_bid.revealed = true;
+        self.sealed_bid.revealed_lamports = lamports;
+        self.asset_auction.record_sealed_reveal(self.sealed_bid.bidder, self.sealed_bid.funder, lamports);
+
+        Ok(SealedBidRevealed {
+            config: self.config.key(),
+            collection: self.asset_auction.collection,
+            asset: self.asset_auction.core_asset,
+            asset_auction: self.asset_auction.key(),
+            bidder: self.sealed_bid.bidder,
+            bid_lamports: lamports,
+            timestamp: unix_timestamp,
+        })
+    }
+}
\ No newline at end of file
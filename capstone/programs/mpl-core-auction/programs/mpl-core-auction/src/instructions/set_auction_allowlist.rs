@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use mpl_core::{accounts::BaseCollectionV1, types::UpdateAuthority};
+
+use crate::{AssetAuction, AuctionErrors, AuctionKind, CollectionAuction, Config};
+
+#[derive(Accounts)]
+pub struct SetAuctionAllowlist<'info> {
+    // EXTERNAL ACCOUNTS
+    pub authority: Signer<'info>,
+    // none for standalone assets of an update authority whitelist
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+    #[account(
+        mut,
+        seeds = [collection_auction.key().as_ref(), asset_auction.core_asset.as_ref()],
+        bump = asset_auction.bump,
+    )]
+    pub asset_auction: Account<'info, AssetAuction>,
+}
+
+
+impl<'info> SetAuctionAllowlist<'info> {
+    pub fn set_auction_allowlist(&mut self, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        let collection = self.collection.as_ref().map(|collection| collection.to_account_info());
+        self.collection_auction.check_collection(collection.as_ref())?;
+
+        // the seller or the curator makes an auction private, the curator is the collection
+        // update authority or the update authority of standalone assets
+        let curator = match self.collection_auction.asset_update_authority() {
+            UpdateAuthority::Collection(_) => self.collection.as_ref().map(|collection| collection.update_authority),
+            UpdateAuthority::Address(address) => Some(address),
+            UpdateAuthority::None => None,
+        };
+        let authority = self.authority.key();
+        require!(
+            authority == self.asset_auction.owner || Some(authority) == curator,
+            AuctionErrors::InvalidAllowlistAuthority
+        );
+
+        // english auctions only, the allowlist can not change once bidding started
+        require!(self.asset_auction.kind == AuctionKind::English, AuctionErrors::InvalidAuctionKind);
+        require!(self.asset_auction.first_bid_timestamp == 0, AuctionErrors::AuctionStarted);
+
+        self.asset_auction.allowlist_root = allowlist_root;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{BidGate, CollectionAuction, Config, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct SetCollectionGate<'info> {
+    // EXTERNAL ACCOUNTS
+    pub admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"collection", config.key().as_ref(), collection_auction.collection.as_ref()],
+        bump = collection_auction.bump,
+    )]
+    pub collection_auction: Account<'info, CollectionAuction>,
+}
+
+
+impl<'info> SetCollectionGate<'info> {
+    pub fn set_collection_gate(&mut self, gate: Option<BidGate>) -> Result<()> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+        if let Some(gate) = gate {
+            require!(gate.is_valid(), AuctionErrors::InvalidGate);
+        }
+
+        // holders only access, clearing the gate opens bidding to the public
+        self.collection_auction.gate = gate;
+        Ok(())
+    }
+}
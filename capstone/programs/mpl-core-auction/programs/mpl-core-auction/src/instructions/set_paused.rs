@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    // EXTERNAL ACCOUNTS
+    pub authority: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+
+impl<'info> SetPaused<'info> {
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        // admin or pauser can flip the circuit breaker
+        let authority = self.authority.key();
+        require!(authority == self.config.admin || authority == self.config.pauser, AuctionErrors::InvalidPauser);
+        self.config.paused = paused;
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct SetPauser<'info> {
+    // EXTERNAL ACCOUNTS
+    pub admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+
+impl<'info> SetPauser<'info> {
+    pub fn set_pauser(&mut self, pauser: Pubkey) -> Result<()> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+        self.config.pauser = pauser;
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, ReferrerFee, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct SetReferrerFee<'info> {
+    // EXTERNAL ACCOUNTS
+    pub admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+
+impl<'info> SetReferrerFee<'info> {
+    pub fn set_referrer_fee(&mut self, referrer_fee: Option<ReferrerFee>) -> Result<()> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+        if let Some(referrer_fee) = referrer_fee {
+            require!(referrer_fee.is_valid(), AuctionErrors::InvalidReferrerFee);
+        }
+        self.config.referrer_fee = referrer_fee;
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, MAX_FEE_BPS, ExtensionParams, BidIncrement, KeeperReward, AuctionErrors};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    // EXTERNAL ACCOUNTS
+    pub admin: Signer<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, fee_bps: u16, min_duration_minutes: u32, max_duration_minutes: u32, extension: ExtensionParams, min_bid_increment: BidIncrement, keeper_reward: Option<KeeperReward>) -> Result<()> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+        require!(fee_bps <= MAX_FEE_BPS, AuctionErrors::InvalidFee);
+        require!(max_duration_minutes > min_duration_minutes, AuctionErrors::InvalidMinMaxDuration);
+        require!(extension.is_valid(), AuctionErrors::InvalidExtension);
+        if let Some(keeper_reward) = keeper_reward {
+            require!(keeper_reward.is_valid(), AuctionErrors::InvalidKeeperReward);
+        }
+
+        // running auctions keep their duration, new params apply to bids and settlements from now on
+        self.config.fee_bps = fee_bps;
+        self.config.min_duration_minutes = min_duration_minutes;
+        self.config.max_duration_minutes = max_duration_minutes;
+        self.config.extension = extension;
+        self.config.min_bid_increment = min_bid_increment;
+        self.config.keeper_reward = keeper_reward;
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{AuctionErrors, BidRefund, Config, TokenVault, RefundWithdrawn, transfer_from_vault};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawRefund<'info> {
+    // EXTERNAL ACCOUNTS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = bid_refund.funder,
+    )]
+    pub funder: UncheckedAccount<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(
+        mut,
+        address = bid_refund.rent_payer,
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"vault", config.key().as_ref()],
+        bump = config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = config,
+        seeds = [b"refund", bid_refund.asset_auction.as_ref(), bid_refund.bidder.as_ref(), bid_refund.funder.as_ref()],
+        bump = bid_refund.bump,
+    )]
+    pub bid_refund: Account<'info, BidRefund>,
+
+    // QUOTE MINT ACCOUNTS, only for refunds paid in a quote mint
+    #[account(
+        constraint = bid_refund.quote_mint == Some(quote_mint.key()) @ AuctionErrors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = quote_mint,
+        associated_token::authority = funder,
+        associated_token::token_program = token_program,
+    )]
+    pub funder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+}
+
+impl<'info> WithdrawRefund<'info> {
+    pub fn withdraw_refund(&mut self) -> Result<RefundWithdrawn> {
+        // the funder or the bidder it funded can withdraw, funds always go back to the funder
+        let authority = self.authority.key();
+        require!(authority == self.bid_refund.funder || authority == self.bid_refund.bidder, AuctionErrors::InvalidRefundAuthority);
+
+        // outbid funds are held by the vault, in lamports or in the quote mint
+        let lamports = self.bid_refund.lamports;
+        match self.bid_refund.quote_mint {
+            None => transfer_from_vault(
+                &self.config,
+                &self.vault.to_account_info(),
+                &self.funder.to_account_info(),
+                lamports,
+            )?,
+            Some(_) => match (&self.quote_mint, &self.vault_token_account, &self.funder_token_account, &self.token_program) {
+                (Some(mint), Some(vault_token_account), Some(funder_token_account), Some(token_program)) => TokenVault {
+                    config: &self.config,
+                    vault: &self.vault,
+                    mint,
+                    vault_token_account,
+                    token_program,
+                }.withdraw(funder_token_account, lamports)?,
+                _ => return err!(AuctionErrors::MissingTokenAccounts),
+            },
+        };
+
+        // refund account is closed on exit, rent goes back to the bidder that paid it
+        Ok(RefundWithdrawn {
+            config: self.config.key(),
+            auction: self.bid_refund.asset_auction,
+            bidder: self.bid_refund.bidder,
+            funder: self.bid_refund.funder,
+            quote_mint: self.bid_refund.quote_mint,
+            lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+
+use crate::{Config, AuctionErrors, transfer_from_tresuary};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    // EXTERNAL ACCOUNTS
+    pub admin: Signer<'info>,
+    /// CHECK: No signature or program ownership needed
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    // INTERNAL ACCOUNTS
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"tresuary", config.key().as_ref()],
+        bump = config.tresuary_bump,
+    )]
+    pub tresuary: SystemAccount<'info>,
+
+    // QUOTE MINT ACCOUNTS, only to withdraw fees paid in a quote mint
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = tresuary,
+        associated_token::token_program = token_program,
+    )]
+    pub tresuary_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::token_program = token_program,
+    )]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+
+impl<'info> WithdrawTreasury<'info> {
+    pub fn withdraw_treasury(&mut self, amount: u64) -> Result<()> {
+        require!(self.admin.key() == self.config.admin, AuctionErrors::InvalidAdmin);
+
+        match (&self.quote_mint, &self.tresuary_token_account, &self.destination_token_account, &self.token_program) {
+            // withdraw lamports, tresuary stays rent exempt
+            (None, _, _, _) => {
+                let rent_exempt_lamports = Rent::get()?.minimum_balance(0);
+                let required_lamports = amount.checked_add(rent_exempt_lamports).ok_or(AuctionErrors::MathOverflow)?;
+                require!(self.tresuary.lamports() >= required_lamports, AuctionErrors::InsufficientTreasury);
+
+                transfer_from_tresuary(&self.config, &self.tresuary.to_account_info(), &self.destination.to_account_info(), amount)?;
+            }
+            // withdraw quote mint fees
+            (Some(mint), Some(tresuary_token_account), Some(destination_token_account), Some(token_program)) => {
+                // tresuary pda signer seeds
+                let signer_seeds: [&[&[u8]]; 1] = [&[
+                    b"tresuary",
+                    self.config.to_account_info().key.as_ref(),
+                    &[self.config.tresuary_bump],
+                ]];
+
+                let token_program_transfer_accounts = TransferChecked {
+                    from: tresuary_token_account.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: destination_token_account.to_account_info(),
+                    authority: self.tresuary.to_account_info(),
+                };
+
+                let cpi_context = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    token_program_transfer_accounts,
+                    &signer_seeds,
+                );
+
+                transfer_checked(cpi_context, amount, mint.decimals)?;
+            }
+            _ => return err!(AuctionErrors::MissingTokenAccounts),
+        };
+
+        Ok(())
+    }
+}
@@ -3,10 +3,14 @@ use anchor_lang::prelude::*;
 mod state;
 mod instructions;
 mod errors;
+mod helpers;
+mod events;
 
-use state::*;
+pub use state::*;
 use instructions::*;
 use errors::*;
+use helpers::*;
+pub use events::*;
 
 
 declare_id!("T8Bk6U2jRGNkqDqtvjGvKGqBzvurwx7gTdmag6jQupL");
@@ -16,28 +20,246 @@ declare_id!("T8Bk6U2jRGNkqDqtvjGvKGqBzvurwx7gTdmag6jQupL");
 pub mod mpl_core_auction {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, seed: u32, fee_bps: u8, min_duration_minutes: u32, max_duration_minutes: u32) -> Result<()> {
-        ctx.accounts.initialize(seed, fee_bps, min_duration_minutes, max_duration_minutes, &ctx.bumps)
+    pub fn initialize(ctx: Context<Initialize>, seed: u32, fee_bps: u16, min_duration_minutes: u32, max_duration_minutes: u32, extension: ExtensionParams, min_bid_increment: BidIncrement) -> Result<()> {
+        let event = ctx.accounts.initialize(seed, fee_bps, min_duration_minutes, max_duration_minutes, extension, min_bid_increment, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
-    pub fn create_collection_auction(ctx: Context<CreateCollectionAuction>) -> Result<()> {
-        ctx.accounts.create_collection_auction(&ctx.bumps)
+    pub fn create_collection_auction(ctx: Context<CreateCollectionAuction>, extension: Option<ExtensionParams>) -> Result<()> {
+        let event = ctx.accounts.create_collection_auction(extension, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
-    pub fn create_asset_auction(ctx: Context<CreateAssetAuction>, duration_minutes: u32, min_bid_lamports: u64) -> Result<()> {
-        ctx.accounts.create_asset_auction(duration_minutes, min_bid_lamports, &ctx.bumps)
+    pub fn create_asset_auction(ctx: Context<CreateAssetAuction>, duration_minutes: u32, min_bid_lamports: u64, buy_now_lamports: Option<u64>, buy_now_cutoff_lamports: Option<u64>, schedule: Option<AuctionSchedule>) -> Result<()> {
+        let event = ctx.accounts.create_asset_auction(duration_minutes, min_bid_lamports, buy_now_lamports, buy_now_cutoff_lamports, schedule, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
     pub fn cancel_asset_auction(ctx: Context<CancelAssetAuction>) -> Result<()> {
-        ctx.accounts.cancel_asset_auction()
+        let event = ctx.accounts.cancel_asset_auction()?;
+        emit_cpi!(event);
+        Ok(())
     }
 
-    pub fn bid_asset_auction(ctx: Context<BidAssetAuction>, lamports: u64) -> Result<()> {
-        ctx.accounts.bid_asset_auction(lamports)
+    pub fn expire_asset_auction(ctx: Context<ExpireAssetAuction>) -> Result<()> {
+        let event = ctx.accounts.expire_asset_auction()?;
+        emit_cpi!(event);
+        Ok(())
     }
 
-    pub fn complete_asset_auction(ctx: Context<CompleteAssetAuction>) -> Result<()> {
-        ctx.accounts.complete_asset_auction()
+    pub fn bid_asset_auction(ctx: Context<BidAssetAuction>, lamports: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let event = ctx.accounts.bid_asset_auction(lamports, proof, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
     }
-}
 
+    pub fn complete_asset_auction<'info>(ctx: Context<'_, '_, '_, 'info, CompleteAssetAuction<'info>>) -> Result<()> {
+        let event = ctx.accounts.complete_asset_auction(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn create_dutch_asset_auction(ctx: Context<CreateDutchAssetAuction>, duration_minutes: u32, start_price_lamports: u64, floor_price_lamports: u64, decay: PriceDecay) -> Result<()> {
+        let event = ctx.accounts.create_dutch_asset_auction(duration_minutes, start_price_lamports, floor_price_lamports, decay, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn buy_dutch_asset_auction<'info>(ctx: Context<'_, '_, '_, 'info, BuyDutchAssetAuction<'info>>, max_price_lamports: u64) -> Result<()> {
+        let event = ctx.accounts.buy_dutch_asset_auction(max_price_lamports, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn create_sealed_asset_auction(ctx: Context<CreateSealedAssetAuction>, commit_minutes: u32, reveal_minutes: u32, min_bid_lamports: u64, unrevealed_penalty_bps: u16) -> Result<()> {
+        let event = ctx.accounts.create_sealed_asset_auction(commit_minutes, reveal_minutes, min_bid_lamports, unrevealed_penalty_bps, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn commit_sealed_bid(ctx: Context<CommitSealedBid>, commitment: [u8; 32], deposit_lamports: u64) -> Result<()> {
+        let event = ctx.accounts.commit_sealed_bid(commitment, deposit_lamports, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn reveal_sealed_bid(ctx: Context<RevealSealedBid>, lamports: u64, salt: [u8; 32]) -> Result<()> {
+        let event = ctx.accounts.reveal_sealed_bid(lamports, salt)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn complete_sealed_asset_auction<'info>(ctx: Context<'_, '_, '_, 'info, CompleteSealedAssetAuction<'info>>) -> Result<()> {
+        match ctx.accounts.complete_sealed_asset_auction(ctx.remaining_accounts)? {
+            SealedSettlement::Sold(event) => emit_cpi!(event),
+            SealedSettlement::Unsold(event) => emit_cpi!(event),
+        }
+        Ok(())
+    }
+
+    pub fn refund_sealed_bid(ctx: Context<RefundSealedBid>) -> Result<()> {
+        let event = ctx.accounts.refund_sealed_bid()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn buy_now_asset_auction<'info>(ctx: Context<'_, '_, '_, 'info, BuyNowAssetAuction<'info>>, proof: Vec<[u8; 32]>) -> Result<()> {
+        let event = ctx.accounts.buy_now_asset_auction(proof, ctx.remaining_accounts, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+    pub fn update_config(ctx: Context<UpdateConfig>, fee_bps: u16, min_duration_minutes: u32, max_duration_minutes: u32, extension: ExtensionParams, min_bid_increment: BidIncrement, keeper_reward: Option<KeeperReward>) -> Result<()> {
+        ctx.accounts.update_config(fee_bps, min_duration_minutes, max_duration_minutes, extension, min_bid_increment, keeper_reward)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_treasury(amount)
+    }
+    pub fn pause_collection(ctx: Context<PauseCollection>) -> Result<()> {
+        ctx.accounts.pause_collection()
+    }
+
+    pub fn resume_collection(ctx: Context<ResumeCollection>) -> Result<()> {
+        ctx.accounts.resume_collection()
+    }
+
+    pub fn remove_collection_auction(ctx: Context<RemoveCollectionAuction>) -> Result<()> {
+        ctx.accounts.remove_collection_auction()
+    }
+    pub fn set_pauser(ctx: Context<SetPauser>, pauser: Pubkey) -> Result<()> {
+        ctx.accounts.set_pauser(pauser)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.set_paused(paused)
+    }
+
+    pub fn emergency_refund_asset_auction(ctx: Context<EmergencyRefundAssetAuction>) -> Result<()> {
+        let event = ctx.accounts.emergency_refund_asset_auction()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn withdraw_refund(ctx: Context<WithdrawRefund>) -> Result<()> {
+        let event = ctx.accounts.withdraw_refund()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn make_offer(ctx: Context<MakeOffer>, lamports: u64, expiry_timestamp: i64) -> Result<()> {
+        let event = ctx.accounts.make_offer(lamports, expiry_timestamp, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        let event = ctx.accounts.cancel_offer()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn accept_offer<'info>(ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>) -> Result<()> {
+        let event = ctx.accounts.accept_offer(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn make_collection_offer(ctx: Context<MakeCollectionOffer>, lamports: u64, quantity: u32, expiry_timestamp: i64) -> Result<()> {
+        let event = ctx.accounts.make_collection_offer(lamports, quantity, expiry_timestamp, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn cancel_collection_offer(ctx: Context<CancelCollectionOffer>) -> Result<()> {
+        let event = ctx.accounts.cancel_collection_offer()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn fill_collection_offer<'info>(ctx: Context<'_, '_, '_, 'info, FillCollectionOffer<'info>>) -> Result<()> {
+        let event = ctx.accounts.fill_collection_offer(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn make_trait_offer(ctx: Context<MakeTraitOffer>, seed: u32, lamports: u64, traits: Vec<Trait>, expiry_timestamp: i64) -> Result<()> {
+        let event = ctx.accounts.make_trait_offer(seed, lamports, traits, expiry_timestamp, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn cancel_trait_offer(ctx: Context<CancelTraitOffer>) -> Result<()> {
+        let event = ctx.accounts.cancel_trait_offer()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn accept_trait_offer<'info>(ctx: Context<'_, '_, '_, 'info, AcceptTraitOffer<'info>>) -> Result<()> {
+        let event = ctx.accounts.accept_trait_offer(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn create_lot_auction<'info>(ctx: Context<'_, '_, '_, 'info, CreateLotAuction<'info>>, seed: u32, duration_minutes: u32, min_bid_lamports: u64) -> Result<()> {
+        let event = ctx.accounts.create_lot_auction(seed, duration_minutes, min_bid_lamports, ctx.remaining_accounts, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn bid_lot_auction(ctx: Context<BidLotAuction>, lamports: u64) -> Result<()> {
+        let event = ctx.accounts.bid_lot_auction(lamports, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn cancel_lot_auction<'info>(ctx: Context<'_, '_, '_, 'info, CancelLotAuction<'info>>) -> Result<()> {
+        let event = ctx.accounts.cancel_lot_auction(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn complete_lot_auction<'info>(ctx: Context<'_, '_, '_, 'info, CompleteLotAuction<'info>>) -> Result<()> {
+        let event = ctx.accounts.complete_lot_auction(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn emergency_refund_lot_auction<'info>(ctx: Context<'_, '_, '_, 'info, EmergencyRefundLotAuction<'info>>) -> Result<()> {
+        let event = ctx.accounts.emergency_refund_lot_auction(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn set_auction_allowlist(ctx: Context<SetAuctionAllowlist>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        ctx.accounts.set_auction_allowlist(allowlist_root)
+    }
+
+    pub fn set_collection_gate(ctx: Context<SetCollectionGate>, gate: Option<BidGate>) -> Result<()> {
+        ctx.accounts.set_collection_gate(gate)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        ctx.accounts.migrate_config()
+    }
+
+    pub fn set_referrer_fee(ctx: Context<SetReferrerFee>, referrer_fee: Option<ReferrerFee>) -> Result<()> {
+        ctx.accounts.set_referrer_fee(referrer_fee)
+    }
+
+    pub fn create_authority_auction(ctx: Context<CreateAuthorityAuction>, extension: Option<ExtensionParams>) -> Result<()> {
+        let event = ctx.accounts.create_authority_auction(extension, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+}
\ No newline at end of file
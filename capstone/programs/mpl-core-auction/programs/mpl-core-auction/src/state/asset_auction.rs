@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{allowlist_leaf, verify_proof};
+
 
 #[account]
 #[derive(InitSpace)]
@@ -7,10 +9,156 @@ pub struct AssetAuction {
     pub collection: Pubkey,
     pub core_asset: Pubkey,
     pub owner: Pubkey,
+    pub kind: AuctionKind,
     pub duration_minutes: u32,
     pub min_bid_lamports: u64,
     pub buyer: Pubkey,
+    pub funder: Pubkey, // payer of the current high bid, outbid funds are credited back to it
     pub buyer_bid_lamports: u64,
     pub first_bid_timestamp: i64,
+    pub start_timestamp: i64, // scheduled auctions only, 0 when the clock starts at the first bid
+    pub end_timestamp: i64,
+    pub extensions: u16,
+    pub buy_now_lamports: Option<u64>,
+    pub buy_now_cutoff_lamports: Option<u64>, // buy now is disabled once the current bid reaches it
+    pub allowlist_root: Option<[u8; 32]>, // only bidders in this merkle allowlist can bid when set
+    pub listing_referrer: Option<Pubkey>, // frontend that brought the listing, gets a share of the fee
+    pub bid_referrer: Option<Pubkey>, // frontend that brought the current high bid, gets a share of the fee
     pub bump: u8,
+}
+
+impl AssetAuction {
+    // scheduled auctions run between fixed start and end timestamps
+    pub fn is_scheduled(&self) -> bool {
+        self.start_timestamp != 0
+    }
+
+    // private auctions only accept bidders with a proof against the allowlist root
+    pub fn is_allowed(&self, bidder: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        match self.allowlist_root {
+            None => true,
+            Some(root) => verify_proof(proof, root, allowlist_leaf(bidder)),
+        }
+    }
+
+    // buy now price if it is still available for the current bid
+    pub fn buy_now_price(&self) -> Option<u64> {
+        let buy_now_lamports = self.buy_now_lamports?;
+        if buy_now_lamports <= self.buyer_bid_lamports {
+            return None;
+        }
+        match self.buy_now_cutoff_lamports {
+            Some(cutoff_lamports) if self.buyer_bid_lamports >= cutoff_lamports => None,
+            _ => Some(buy_now_lamports),
+        }
+    }
+
+    // current price of a dutch auction, None for other auction kinds
+    pub fn dutch_price(&self, unix_timestamp: i64) -> Option<u64> {
+        let AuctionKind::Dutch { start_price_lamports, floor_price_lamports, decay, start_timestamp } = self.kind else {
+            return None;
+        };
+
+        let duration = self.duration_minutes as i64 * 60;
+        let elapsed = (unix_timestamp - start_timestamp).clamp(0, duration);
+        let (elapsed, duration) = match decay {
+            PriceDecay::Linear => (elapsed, duration),
+            PriceDecay::Stepped { step_minutes } => {
+                let step = step_minutes as i64 * 60;
+                (elapsed / step, duration / step)
+            }
+        };
+        if duration == 0 {
+            return Some(floor_price_lamports);
+        }
+
+        let price_drop = (start_price_lamports - floor_price_lamports) as u128 * elapsed as u128 / duration as u128;
+        Some(start_price_lamports - price_drop as u64)
+    }
+
+    // sealed bids are ranked as they are revealed, winner pays the second price
+    pub fn record_sealed_reveal(&mut self, bidder: Pubkey, funder: Pubkey, lamports: u64) {
+        let AuctionKind::Sealed { ref mut second_bid_lamports, .. } = self.kind else {
+            return;
+        };
+
+        // bids under the reserve can not win
+        if lamports < self.min_bid_lamports {
+            return;
+        }
+
+        if lamports > self.buyer_bid_lamports {
+            *second_bid_lamports = self.buyer_bid_lamports;
+            self.buyer = bidder;
+            self.funder = funder;
+            self.buyer_bid_lamports = lamports;
+        } else if lamports > *second_bid_lamports {
+            *second_bid_lamports = lamports;
+        }
+    }
+
+    // mark a sealed auction as settled, returns the bids still open
+    pub fn settle_sealed(&mut self) -> u32 {
+        match self.kind {
+            AuctionKind::Sealed { ref mut settled, open_bids, .. } => {
+                *settled = true;
+                open_bids
+            }
+            _ => 0,
+        }
+    }
+
+    // remove a settled sealed bid, returns the bids still open
+    pub fn close_sealed_bid(&mut self) -> u32 {
+        match self.kind {
+            AuctionKind::Sealed { ref mut open_bids, .. } => {
+                *open_bids -= 1;
+                *open_bids
+            }
+            _ => 0,
+        }
+    }
+
+    // price paid by the sealed auction winner, never lower than the reserve
+    pub fn sealed_price(&self) -> Option<u64> {
+        match self.kind {
+            AuctionKind::Sealed { second_bid_lamports, .. } => Some(second_bid_lamports.max(self.min_bid_lamports)),
+            _ => None,
+        }
+    }
+}
+
+
+// fixed auction window, bids are only accepted between start_ts and end_ts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct AuctionSchedule {
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AuctionKind {
+    English,
+    Dutch {
+        start_price_lamports: u64,
+        floor_price_lamports: u64,
+        decay: PriceDecay,
+        start_timestamp: i64,
+    },
+    // commit phase until commit_end_timestamp, reveal phase until end_timestamp
+    Sealed {
+        commit_end_timestamp: i64,
+        unrevealed_penalty_bps: u16,
+        second_bid_lamports: u64,
+        open_bids: u32,
+        settled: bool,
+    },
+}
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PriceDecay {
+    Linear,
+    Stepped { step_minutes: u32 },
 }
\ No newline at end of file
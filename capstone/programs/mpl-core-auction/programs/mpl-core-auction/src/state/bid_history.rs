@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+
+// number of bids kept per english auction, the account is sized for it at creation
+pub const BID_HISTORY_LEN: usize = 32;
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct BidEntry {
+    pub bidder: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+// past bids of an english asset auction, closed with the auction
+#[account]
+#[derive(InitSpace)]
+pub struct BidHistory {
+    pub asset_auction: Pubkey,
+    pub total_bids: u32, // every bid placed, the list only keeps the latest ones
+    #[max_len(BID_HISTORY_LEN)]
+    pub bids: Vec<BidEntry>, // oldest first
+    pub bump: u8,
+}
+
+impl BidHistory {
+    // append a bid, the oldest one is dropped once the list is full
+    pub fn push(&mut self, bidder: Pubkey, lamports: u64, timestamp: i64) {
+        if self.bids.len() == BID_HISTORY_LEN {
+            self.bids.remove(0);
+        }
+        self.bids.push(BidEntry { bidder, lamports, timestamp });
+        self.total_bids += 1;
+    }
+}
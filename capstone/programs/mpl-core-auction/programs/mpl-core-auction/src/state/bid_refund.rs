@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{AssetAuction, LotAuction};
+
+
+// outbid funds of a bidder, they stay in the vault until the funder withdraws them
+#[account]
+#[derive(InitSpace)]
+pub struct BidRefund {
+    pub config: Pubkey,
+    pub asset_auction: Pubkey,
+    pub bidder: Pubkey,
+    pub funder: Pubkey, // payer of the outbid bid, receives the refund
+    pub rent_payer: Pubkey, // payer of the new bid that created this account, gets the rent back
+    pub quote_mint: Option<Pubkey>, // refund is paid in the collection quote mint when set
+    pub lamports: u64,
+    pub bump: u8,
+}
+
+impl BidRefund {
+    // credit the current high bid of an auction before it is replaced,
+    // refund accounts are keyed by bidder and funder so funds only go back to who paid them
+    pub fn credit(&mut self, config: Pubkey, asset_auction_key: Pubkey, asset_auction: &AssetAuction, quote_mint: Option<Pubkey>, rent_payer: Pubkey, bump: Option<u8>) {
+        if self.asset_auction == Pubkey::default() {
+            self.config = config;
+            self.asset_auction = asset_auction_key;
+            self.bidder = asset_auction.buyer;
+            self.funder = asset_auction.funder;
+            self.rent_payer = rent_payer;
+            self.quote_mint = quote_mint;
+            self.bump = bump.unwrap_or_default();
+        }
+        self.lamports += asset_auction.buyer_bid_lamports;
+    }
+
+    // credit the current high bid of a lot, lot refunds are keyed by the lot account
+    pub fn credit_lot(&mut self, config: Pubkey, lot_auction_key: Pubkey, lot_auction: &LotAuction, rent_payer: Pubkey, bump: Option<u8>) {
+        if self.asset_auction == Pubkey::default() {
+            self.config = config;
+            self.asset_auction = lot_auction_key;
+            self.bidder = lot_auction.buyer;
+            self.funder = lot_auction.funder;
+            self.rent_payer = rent_payer;
+            self.quote_mint = None;
+            self.bump = bump.unwrap_or_default();
+        }
+        self.lamports += lot_auction.buyer_bid_lamports;
+    }
+}
@@ -1,9 +1,90 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use mpl_core::{accounts::BaseAssetV1, types::UpdateAuthority};
+
+use crate::{AuctionErrors, ExtensionParams};
 
 
 #[account]
 #[derive(InitSpace)]
 pub struct CollectionAuction {
-    pub collection: Pubkey,
+    pub collection: Pubkey, // core collection, or update authority address of standalone assets
+    pub kind: WhitelistKind,
+    pub extension: Option<ExtensionParams>, // overrides config extension params when set
+    pub quote_mint: Option<Pubkey>, // bids are paid in this mint instead of lamports when set
+    pub enabled: bool, // paused collections block new auctions and bids
+    pub open_auctions: u32,
+    pub gate: Option<BidGate>, // only holders can bid when set
     pub bump: u8,
 }
+
+impl CollectionAuction {
+    // update authority of the assets that can be listed under this whitelist
+    pub fn asset_update_authority(&self) -> UpdateAuthority {
+        match self.kind {
+            WhitelistKind::Collection => UpdateAuthority::Collection(self.collection),
+            WhitelistKind::UpdateAuthority => UpdateAuthority::Address(self.collection),
+        }
+    }
+
+    // every open auction was counted when it was created, going below zero is a counting bug
+    pub fn close_auction(&mut self) -> Result<()> {
+        self.open_auctions = self.open_auctions.checked_sub(1).ok_or(AuctionErrors::MathOverflow)?;
+        Ok(())
+    }
+
+    // core collection account passed to mpl-core, none for standalone assets
+    pub fn check_collection(&self, collection: Option<&AccountInfo>) -> Result<()> {
+        let expected = match self.kind {
+            WhitelistKind::Collection => Some(self.collection),
+            WhitelistKind::UpdateAuthority => None,
+        };
+        require!(collection.map(|collection| collection.key()) == expected, AuctionErrors::InvalidCollection);
+        Ok(())
+    }
+
+    // gated collections only take bids from holders, checked against accounts passed at bid time
+    pub fn check_gate(&self, bidder: &Pubkey, gate_asset: Option<&BaseAssetV1>, gate_token_account: Option<&TokenAccount>) -> Result<()> {
+        match self.gate {
+            None => Ok(()),
+            Some(BidGate::Collection { collection }) => {
+                let gate_asset = gate_asset.ok_or(AuctionErrors::MissingGateAccount)?;
+                require!(gate_asset.owner == *bidder, AuctionErrors::GateNotMet);
+                require!(gate_asset.update_authority == UpdateAuthority::Collection(collection), AuctionErrors::GateNotMet);
+                Ok(())
+            }
+            Some(BidGate::Token { mint, min_amount }) => {
+                let gate_token_account = gate_token_account.ok_or(AuctionErrors::MissingGateAccount)?;
+                require!(gate_token_account.owner == *bidder, AuctionErrors::GateNotMet);
+                require!(gate_token_account.mint == mint, AuctionErrors::GateNotMet);
+                require!(gate_token_account.amount >= min_amount, AuctionErrors::GateNotMet);
+                Ok(())
+            }
+        }
+    }
+}
+
+
+// whitelisted assets belong to a core collection or share an update authority address
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum WhitelistKind {
+    Collection,
+    UpdateAuthority,
+}
+
+
+// holding rule a bidder has to meet: one asset of a core collection or a balance of a mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BidGate {
+    Collection { collection: Pubkey },
+    Token { mint: Pubkey, min_amount: u64 },
+}
+
+impl BidGate {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            BidGate::Collection { .. } => true,
+            BidGate::Token { min_amount, .. } => *min_amount > 0,
+        }
+    }
+}
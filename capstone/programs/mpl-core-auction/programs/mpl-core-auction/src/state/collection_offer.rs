@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+
+// floor bid on any asset of a collection, the lamports of the unfilled units stay in the vault
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionOffer {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub bidder: Pubkey,
+    pub lamports: u64, // price of a single unit
+    pub quantity: u32,
+    pub filled: u32,
+    pub expiry_timestamp: i64,
+    pub bump: u8,
+}
+
+impl CollectionOffer {
+    pub fn remaining(&self) -> u32 {
+        self.quantity - self.filled
+    }
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+
+// number of recent sales kept per collection
+pub const RECENT_SALES: usize = 16;
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct Sale {
+    pub asset: Pubkey,
+    pub price_lamports: u64,
+    pub timestamp: i64,
+}
+
+// sales of a collection auction, prices are in the collection quote mint when set
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionStats {
+    pub collection_auction: Pubkey,
+    pub volume_lamports: u128,
+    pub sales: u64,
+    pub last_price_lamports: u64,
+    pub last_sale_timestamp: i64,
+    pub window_min_lamports: u64, // lowest price of the recent sales
+    pub window_max_lamports: u64, // highest price of the recent sales
+    pub recent_sales: [Sale; RECENT_SALES], // ring buffer, next sale goes to sales % RECENT_SALES
+    pub bump: u8,
+}
+
+impl CollectionStats {
+    pub fn new(collection_auction: Pubkey, bump: u8) -> Self {
+        CollectionStats {
+            collection_auction,
+            volume_lamports: 0,
+            sales: 0,
+            last_price_lamports: 0,
+            last_sale_timestamp: 0,
+            window_min_lamports: 0,
+            window_max_lamports: 0,
+            recent_sales: [Sale::default(); RECENT_SALES],
+            bump,
+        }
+    }
+
+    // record a completed sale, oldest recent sale is overwritten once the buffer is full
+    pub fn record(&mut self, asset: Pubkey, price_lamports: u64, timestamp: i64) {
+        let slot = (self.sales % RECENT_SALES as u64) as usize;
+        self.recent_sales[slot] = Sale { asset, price_lamports, timestamp };
+
+        self.sales += 1;
+        self.volume_lamports += price_lamports as u128;
+        self.last_price_lamports = price_lamports;
+        self.last_sale_timestamp = timestamp;
+
+        let window = &self.recent_sales[..(self.sales as usize).min(RECENT_SALES)];
+        self.window_min_lamports = window.iter().map(|sale| sale.price_lamports).min().unwrap_or_default();
+        self.window_max_lamports = window.iter().map(|sale| sale.price_lamports).max().unwrap_or_default();
+    }
+}
@@ -1,9 +1,107 @@
 use anchor_lang::prelude::*;
 
 
+// upper bound of the protocol fee, 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
+    pub seed: u32,
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // proposed admin, has to accept the role
+    pub pauser: Pubkey, // can pause the program besides the admin
+    pub paused: bool, // blocks listing and bidding, cancels and emergency refunds stay available
+    pub fee_bps: u16,
+    pub min_duration_minutes: u32,
+    pub max_duration_minutes: u32,
+    pub extension: ExtensionParams,
+    pub min_bid_increment: BidIncrement,
+    pub keeper_reward: Option<KeeperReward>, // paid to permissionless settlement callers
+    pub referrer_fee: Option<ReferrerFee>, // paid to the referrers of english auction sales
+    pub vault_bump: u8,
+    pub tresuary_bump: u8,
+    pub bump: u8,
+}
+
+
+// anti-sniping: a bid placed less than window_minutes before the end pushes the end
+// forward by extension_minutes, at most max_extensions times per auction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ExtensionParams {
+    pub window_minutes: u32,
+    pub extension_minutes: u32,
+    pub max_extensions: u16,
+}
+
+impl ExtensionParams {
+    pub fn is_valid(&self) -> bool {
+        self.max_extensions == 0 || (self.window_minutes > 0 && self.extension_minutes > 0)
+    }
+}
+
+
+// minimum raise over the current bid, absolute or relative to the current bid
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub enum BidIncrement {
+    Lamports { amount: u64 },
+    Bps { bps: u16 },
+}
+
+impl BidIncrement {
+    pub fn increment(&self, current_bid_lamports: u64) -> u64 {
+        match *self {
+            BidIncrement::Lamports { amount } => amount,
+            BidIncrement::Bps { bps } => (current_bid_lamports as u128 * bps as u128 / 10_000) as u64,
+        }
+    }
+}
+
+
+// keeper reward, carved out of the protocol fee of a sale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub enum KeeperReward {
+    Lamports { amount: u64 },
+    Bps { bps: u16 }, // share of the fee
+}
+
+impl KeeperReward {
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            KeeperReward::Lamports { .. } => true,
+            KeeperReward::Bps { bps } => bps <= 10_000,
+        }
+    }
+
+    pub fn reward(&self, fee_lamports: u64) -> u64 {
+        match *self {
+            KeeperReward::Lamports { amount } => amount.min(fee_lamports),
+            KeeperReward::Bps { bps } => (fee_lamports as u128 * bps as u128 / 10_000) as u64,
+        }
+    }
+}
+
+
+// referrer shares of the protocol fee, for the referrer of the winning bid and of the listing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct ReferrerFee {
+    pub bid_bps: u16,
+    pub listing_bps: u16,
+}
+
+impl ReferrerFee {
+    pub fn is_valid(&self) -> bool {
+        self.bid_bps as u32 + self.listing_bps as u32 <= 10_000
+    }
+}
+
+
+// legacy config layouts read by migrate_config, told apart by their account size
+
+// baseline layout, before admin handover, pausing, anti-sniping, bid increments,
+// keeper rewards and referrer fees were added and fee_bps was widened
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct ConfigV0 {
     pub seed: u32,
     pub admin: Pubkey,
     pub fee_bps: u8,
@@ -12,4 +110,69 @@ pub struct Config {
     pub vault_bump: u8,
     pub tresuary_bump: u8,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+impl ConfigV0 {
+    // fields missing from the baseline layout start disabled, the admin is also the pauser
+    pub fn migrate(self) -> Config {
+        Config {
+            seed: self.seed,
+            admin: self.admin,
+            pending_admin: None,
+            pauser: self.admin,
+            paused: false,
+            fee_bps: self.fee_bps as u16,
+            min_duration_minutes: self.min_duration_minutes,
+            max_duration_minutes: self.max_duration_minutes,
+            extension: ExtensionParams { window_minutes: 0, extension_minutes: 0, max_extensions: 0 },
+            min_bid_increment: BidIncrement::Lamports { amount: 0 },
+            keeper_reward: None,
+            referrer_fee: None,
+            vault_bump: self.vault_bump,
+            tresuary_bump: self.tresuary_bump,
+            bump: self.bump,
+        }
+    }
+}
+
+
+// layout with fee_bps widened to a u16, before referrer fees were added
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct ConfigV1 {
+    pub seed: u32,
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub pauser: Pubkey,
+    pub paused: bool,
+    pub fee_bps: u16,
+    pub min_duration_minutes: u32,
+    pub max_duration_minutes: u32,
+    pub extension: ExtensionParams,
+    pub min_bid_increment: BidIncrement,
+    pub keeper_reward: Option<KeeperReward>,
+    pub vault_bump: u8,
+    pub tresuary_bump: u8,
+    pub bump: u8,
+}
+
+impl ConfigV1 {
+    pub fn migrate(self) -> Config {
+        Config {
+            seed: self.seed,
+            admin: self.admin,
+            pending_admin: self.pending_admin,
+            pauser: self.pauser,
+            paused: self.paused,
+            fee_bps: self.fee_bps,
+            min_duration_minutes: self.min_duration_minutes,
+            max_duration_minutes: self.max_duration_minutes,
+            extension: self.extension,
+            min_bid_increment: self.min_bid_increment,
+            keeper_reward: self.keeper_reward,
+            referrer_fee: None,
+            vault_bump: self.vault_bump,
+            tresuary_bump: self.tresuary_bump,
+            bump: self.bump,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+
+// max assets in a lot, every asset is locked and released with its own custody cpis
+pub const MAX_LOT_ASSETS: usize = 8;
+
+
+// english auction of several assets of a collection sold together, one bid covers the whole lot
+#[account]
+#[derive(InitSpace)]
+pub struct LotAuction {
+    pub collection: Pubkey,
+    pub owner: Pubkey,
+    pub seed: u32, // an owner can run several lots per collection
+    #[max_len(MAX_LOT_ASSETS)]
+    pub assets: Vec<Pubkey>,
+    pub duration_minutes: u32,
+    pub min_bid_lamports: u64,
+    pub buyer: Pubkey, // owner until the first bid
+    pub funder: Pubkey,
+    pub buyer_bid_lamports: u64,
+    pub first_bid_timestamp: i64,
+    pub end_timestamp: i64,
+    pub extensions: u16,
+    pub bump: u8,
+}
+
+impl LotAuction {
+    // remaining accounts must be the lot assets in order
+    pub fn check_assets(&self, asset_accounts: &[AccountInfo]) -> bool {
+        asset_accounts.len() == self.assets.len()
+            && self.assets.iter().zip(asset_accounts.iter()).all(|(asset, account)| *asset == account.key())
+    }
+}
@@ -1,7 +1,23 @@
 pub mod config;
 pub mod collection_auction;
 pub mod asset_auction;
+pub mod sealed_bid;
+pub mod bid_refund;
+pub mod collection_stats;
+pub mod bid_history;
+pub mod offer;
+pub mod collection_offer;
+pub mod trait_offer;
+pub mod lot_auction;
 
 pub use config::*;
 pub use collection_auction::*;
 pub use asset_auction::*;
+pub use sealed_bid::*;
+pub use bid_refund::*;
+pub use collection_stats::*;
+pub use bid_history::*;
+pub use offer::*;
+pub use collection_offer::*;
+pub use trait_offer::*;
+pub use lot_auction::*;
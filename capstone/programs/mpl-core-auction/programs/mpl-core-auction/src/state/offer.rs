@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+
+// offer on an unlisted asset, the offered lamports stay in the vault until it is accepted or cancelled
+#[account]
+#[derive(InitSpace)]
+pub struct Offer {
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub bidder: Pubkey,
+    pub lamports: u64,
+    pub expiry_timestamp: i64,
+    pub bump: u8,
+}
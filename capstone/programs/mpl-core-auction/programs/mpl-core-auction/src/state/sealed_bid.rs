@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct SealedBid {
+    pub asset_auction: Pubkey,
+    pub bidder: Pubkey,
+    pub funder: Pubkey, // payer of the deposit, receives the refund
+    pub commitment: [u8; 32], // sha256(lamports le bytes, salt)
+    pub deposit_lamports: u64,
+    pub revealed_lamports: u64,
+    pub revealed: bool,
+    pub bump: u8,
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use mpl_core::types::Attribute;
+
+
+// trait criteria per offer and max length of a trait key or value
+pub const MAX_TRAITS: usize = 4;
+pub const MAX_TRAIT_LEN: usize = 32;
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Trait {
+    #[max_len(MAX_TRAIT_LEN)]
+    pub key: String,
+    #[max_len(MAX_TRAIT_LEN)]
+    pub value: String,
+}
+
+impl Trait {
+    pub fn is_valid(&self) -> bool {
+        !self.key.is_empty() && self.key.len() <= MAX_TRAIT_LEN && self.value.len() <= MAX_TRAIT_LEN
+    }
+}
+
+// offer on any asset of a collection auction whose attributes plugin has all the traits,
+// the offered lamports stay in the vault until it is accepted or cancelled
+#[account]
+#[derive(InitSpace)]
+pub struct TraitOffer {
+    pub config: Pubkey,
+    pub collection_auction: Pubkey,
+    pub collection: Pubkey,
+    pub bidder: Pubkey,
+    pub seed: u32, // a bidder can hold several trait offers per collection
+    pub lamports: u64,
+    pub expiry_timestamp: i64,
+    #[max_len(MAX_TRAITS)]
+    pub traits: Vec<Trait>,
+    pub bump: u8,
+}
+
+impl TraitOffer {
+    // every trait must match an attribute of the asset
+    pub fn matches(&self, attributes: &[Attribute]) -> bool {
+        self.traits.iter().all(|required| {
+            attributes.iter().any(|attribute| attribute.key == required.key && attribute.value == required.value)
+        })
+    }
+}
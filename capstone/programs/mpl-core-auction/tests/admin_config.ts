@@ -0,0 +1,239 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+
+
+describe("Auction admin", () => {
+
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // config params
+    const initParams = {
+        seed: 12,
+        feeBPS: 100,
+        minDurationMinutes: 60,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _1] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    it("update config", async () => {
+        const updateParams = {
+            feeBPS: 200,
+            minDurationMinutes: 30,
+            maxDurationMinutes: 7200,
+            extension: {windowMinutes: 5, extensionMinutes: 5, maxExtensions: 3},
+            minBidIncrement: {bps: {bps: 500}},
+            keeperReward: {bps: {bps: 1000}},
+        };
+
+        await program.methods
+            .updateConfig(
+                updateParams.feeBPS,
+                updateParams.minDurationMinutes,
+                updateParams.maxDurationMinutes,
+                updateParams.extension,
+                updateParams.minBidIncrement,
+                updateParams.keeperReward,
+            )
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+
+        // verify values
+        const auction_config = await program.account.config.fetch(auctionConfigPDA);
+        assert(auction_config.feeBps === updateParams.feeBPS);
+        assert(auction_config.minDurationMinutes === updateParams.minDurationMinutes);
+        assert(auction_config.maxDurationMinutes === updateParams.maxDurationMinutes);
+        assert(auction_config.extension.maxExtensions === updateParams.extension.maxExtensions);
+        assert(auction_config.minBidIncrement.bps?.bps === updateParams.minBidIncrement.bps.bps);
+        assert(auction_config.keeperReward?.bps?.bps === updateParams.keeperReward.bps.bps);
+    });
+
+    it("try update config with non admin raises", async () => {
+        const other = await createSigner();
+
+        let failed = false;
+        try {
+            await program.methods
+                .updateConfig(
+                    0,
+                    initParams.minDurationMinutes,
+                    initParams.maxDurationMinutes,
+                    initParams.extension,
+                    initParams.minBidIncrement,
+                    null,
+                )
+                .accountsPartial({config: auctionConfigPDA, admin: other.publicKey})
+                .signers([other])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidAdmin")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("propose and accept admin", async () => {
+        const newAdmin = await createSigner();
+
+        // propose new admin, admin does not change yet
+        await program.methods
+            .proposeAdmin(newAdmin.publicKey)
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+
+        let auction_config = await program.account.config.fetch(auctionConfigPDA);
+        assert(auction_config.admin.toBase58() === provider.wallet.publicKey.toBase58());
+        assert(auction_config.pendingAdmin?.toBase58() === newAdmin.publicKey.toBase58());
+
+        // accept admin role
+        await program.methods
+            .acceptAdmin()
+            .accountsPartial({config: auctionConfigPDA, pendingAdmin: newAdmin.publicKey})
+            .signers([newAdmin])
+            .rpc();
+
+        auction_config = await program.account.config.fetch(auctionConfigPDA);
+        assert(auction_config.admin.toBase58() === newAdmin.publicKey.toBase58());
+        assert(auction_config.pendingAdmin === null);
+
+        // hand admin role back
+        await program.methods
+            .proposeAdmin(provider.wallet.publicKey)
+            .accountsPartial({config: auctionConfigPDA, admin: newAdmin.publicKey})
+            .signers([newAdmin])
+            .rpc();
+        await program.methods
+            .acceptAdmin()
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+
+        auction_config = await program.account.config.fetch(auctionConfigPDA);
+        assert(auction_config.admin.toBase58() === provider.wallet.publicKey.toBase58());
+    });
+
+    it("try accept admin with wrong key raises", async () => {
+        const newAdmin = await createSigner();
+        const other = await createSigner();
+
+        await program.methods
+            .proposeAdmin(newAdmin.publicKey)
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+
+        let failed = false;
+        try {
+            await program.methods
+                .acceptAdmin()
+                .accountsPartial({config: auctionConfigPDA, pendingAdmin: other.publicKey})
+                .signers([other])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidAdmin")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("withdraw treasury", async () => {
+        // fund tresuary
+        const tx = new anchor.web3.Transaction().add(
+            anchor.web3.SystemProgram.transfer({
+                fromPubkey: provider.wallet.publicKey,
+                toPubkey: tresuaryPDA,
+                lamports: anchor.web3.LAMPORTS_PER_SOL,
+            })
+        );
+        await provider.sendAndConfirm(tx);
+
+        // withdraw half of the fees
+        const destination = anchor.web3.Keypair.generate();
+        const amount = new BN(anchor.web3.LAMPORTS_PER_SOL / 2);
+        await program.methods
+            .withdrawTreasury(amount)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({destination: destination.publicKey})
+            .rpc();
+
+        const destinationAccountInfo = await provider.connection.getAccountInfo(destination.publicKey);
+        const tresuaryAccountInfo = await provider.connection.getAccountInfo(tresuaryPDA);
+        assert(destinationAccountInfo?.lamports === amount.toNumber());
+        assert(tresuaryAccountInfo?.lamports === anchor.web3.LAMPORTS_PER_SOL - amount.toNumber());
+    });
+
+    it("try withdraw treasury below rent exempt raises", async () => {
+        const tresuaryAccountInfo = await provider.connection.getAccountInfo(tresuaryPDA);
+
+        let failed = false;
+        try {
+            await program.methods
+                .withdrawTreasury(new BN(tresuaryAccountInfo!.lamports))
+                .accountsPartial({config: auctionConfigPDA})
+                .accounts({destination: provider.wallet.publicKey})
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InsufficientTreasury")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
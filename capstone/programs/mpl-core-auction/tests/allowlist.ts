@@ -0,0 +1,336 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+import { createHash } from "crypto";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Asset auction allowlist", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 25,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+
+    // sha256 of a merkle node pair, pairs are hashed in sorted order
+    function hashPair(a: Buffer, b: Buffer): Buffer {
+        const [first, second] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+        return createHash("sha256").update(first).update(second).digest();
+    }
+
+    // helper function to build a merkle root and a proof for each bidder
+    function buildAllowlist(bidders: anchor.web3.PublicKey[]): {root: number[], proofs: number[][][]} {
+        let level = bidders.map((bidder) => createHash("sha256").update(bidder.toBuffer()).digest());
+        const proofs: number[][][] = bidders.map(() => []);
+        let positions = bidders.map((_, index) => index);
+
+        while (level.length > 1) {
+            // siblings of each leaf at this level
+            positions.forEach((position, index) => {
+                const sibling = position ^ 1;
+                if (sibling < level.length) {
+                    proofs[index].push(Array.from(level[sibling]));
+                }
+            });
+
+            // odd nodes are carried to the next level
+            const next: Buffer[] = [];
+            for (let i = 0; i < level.length; i += 2) {
+                next.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i]);
+            }
+            level = next;
+            positions = positions.map((position) => position >> 1);
+        }
+
+        return {root: Array.from(level[0]), proofs};
+    }
+
+    // helper function to create an english asset auction, returns its pda
+    async function createAssetAuction(assetPubkey: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> {
+        await program.methods
+            .createAssetAuction(60, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        const [assetAuctionPDA, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        return assetAuctionPDA;
+    }
+
+    // helper function to set the allowlist root of an asset auction
+    async function setAllowlist(assetAuctionPDA: anchor.web3.PublicKey, root: number[] | null, authority: anchor.web3.Keypair) {
+        await program.methods
+            .setAuctionAllowlist(root)
+            .accountsPartial({
+                authority: authority.publicKey,
+                config: auctionConfigPDA,
+                collection: collectionPubkey,
+                collectionAuction: auctionCollectionPDA,
+                assetAuction: assetAuctionPDA,
+            })
+            .signers([authority])
+            .rpc();
+    }
+
+    // helper function to bid on an asset auction
+    async function bid(assetPubkey: anchor.web3.PublicKey, buyer: anchor.web3.Keypair, lamports: BN, proof: number[][], first: boolean) {
+        await program.methods
+            .bidAssetAuction(lamports, proof)
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                ...(first ? {previousBidRefund: null} : {}),
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+    }
+
+    it("allowlisted bidders can bid", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        const bidders = [await createSigner(), await createSigner(), await createSigner()];
+        const {root, proofs} = buildAllowlist(bidders.map((bidder) => bidder.publicKey));
+        await setAllowlist(assetAuctionPDA, root, payerWallet.payer);
+
+        let assetAuction = await program.account.assetAuction.fetch(assetAuctionPDA);
+        assert.deepEqual(assetAuction.allowlistRoot, root);
+
+        await bid(assetPubkey, bidders[2], new BN(LAMPORTS_PER_SOL), proofs[2], true);
+        await bid(assetPubkey, bidders[0], new BN(2 * LAMPORTS_PER_SOL), proofs[0], false);
+
+        assetAuction = await program.account.assetAuction.fetch(assetAuctionPDA);
+        assert(assetAuction.buyer.equals(bidders[0].publicKey));
+    });
+
+    it("bidders outside the allowlist can not bid", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        const bidders = [await createSigner(), await createSigner()];
+        const {root, proofs} = buildAllowlist(bidders.map((bidder) => bidder.publicKey));
+        await setAllowlist(assetAuctionPDA, root, payerWallet.payer);
+
+        // an outsider reusing a valid proof is rejected
+        const outsider = await createSigner();
+        let failed = false;
+        try {
+            await bid(assetPubkey, outsider, new BN(LAMPORTS_PER_SOL), proofs[0], true);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "NotAllowlisted")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("removing the allowlist opens the auction", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        const {root} = buildAllowlist([(await createSigner()).publicKey]);
+        await setAllowlist(assetAuctionPDA, root, payerWallet.payer);
+        await setAllowlist(assetAuctionPDA, null, payerWallet.payer);
+
+        const assetAuction = await program.account.assetAuction.fetch(assetAuctionPDA);
+        assert(assetAuction.allowlistRoot == null);
+
+        const buyer = await createSigner();
+        await bid(assetPubkey, buyer, new BN(LAMPORTS_PER_SOL), [], true);
+    });
+
+    it("only the seller or the collection authority can set the allowlist", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        const stranger = await createSigner();
+        const {root} = buildAllowlist([stranger.publicKey]);
+
+        let failed = false;
+        try {
+            await setAllowlist(assetAuctionPDA, root, stranger);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidAllowlistAuthority")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("allowlist can not change after the first bid", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        const buyer = await createSigner();
+        await bid(assetPubkey, buyer, new BN(LAMPORTS_PER_SOL), [], true);
+
+        const {root} = buildAllowlist([buyer.publicKey]);
+        let failed = false;
+        try {
+            await setAllowlist(assetAuctionPDA, root, payerWallet.payer);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "AuctionStarted")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
@@ -0,0 +1,340 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+    publicKey,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Update authority whitelist", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 29,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+
+    // studio address, update authority of standalone game items
+    const studio = anchor.web3.Keypair.generate();
+
+    // update authority whitelist pda, keyed by the studio address
+    const [authorityAuctionPDA, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), studio.publicKey.toBuffer()],
+        program.programId,
+    );
+
+    before("whitelist update authority", async () => {
+        await program.methods
+            .createAuthorityAuction(null)
+            .accountsPartial({config: auctionConfigPDA, updateAuthority: studio.publicKey})
+            .rpc();
+    });
+
+    // helper function to create a standalone asset of an update authority
+    async function createStandaloneAsset(updateAuthority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> {
+        const assetSigner = generateSigner(umi)
+        await create(umi, {
+            asset: assetSigner,
+            name: 'My item',
+            uri: "",
+            updateAuthority: publicKey(updateAuthority.toString()),
+        }).sendAndConfirm(umi);
+        return new anchor.web3.PublicKey(assetSigner.publicKey.toString());
+    }
+
+    // helper function to list a standalone asset
+    async function createAssetAuction(assetPubkey: anchor.web3.PublicKey, collection: anchor.web3.PublicKey | null, buyNow: BN | null = null) {
+        await program.methods
+            .createAssetAuction(0, new BN(100), buyNow, null, null)
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: authorityAuctionPDA,
+                collection,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    it("whitelist standalone assets by update authority", async () => {
+        const authorityAuction = await program.account.collectionAuction.fetch(authorityAuctionPDA);
+        assert(authorityAuction.collection.equals(studio.publicKey));
+        assert(authorityAuction.kind.updateAuthority !== undefined);
+        assert(authorityAuction.openAuctions == 0);
+    });
+
+    it("bid on and complete a standalone asset auction", async () => {
+        const assetPubkey = await createStandaloneAsset(studio.publicKey);
+        await createAssetAuction(assetPubkey, null);
+
+        const buyer = await createSigner();
+        await program.methods
+            .bidAssetAuction(new BN(LAMPORTS_PER_SOL), [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: authorityAuctionPDA,
+                collection: null,
+                asset: assetPubkey,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+            })
+            .signers([buyer])
+            .rpc();
+
+        await program.methods
+            .completeAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: authorityAuctionPDA,
+                collection: null,
+                asset: assetPubkey,
+                owner: signer.publicKey,
+                buyer: buyer.publicKey,
+            })
+            .rpc();
+
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+        assert(asset.freezeDelegate?.authority.type == "Owner");
+
+        const authorityAuction = await program.account.collectionAuction.fetch(authorityAuctionPDA);
+        assert(authorityAuction.openAuctions == 0);
+    });
+
+    it("buy now a standalone asset", async () => {
+        const assetPubkey = await createStandaloneAsset(studio.publicKey);
+        await createAssetAuction(assetPubkey, null, new BN(LAMPORTS_PER_SOL));
+
+        const buyer = await createSigner();
+        await program.methods
+            .buyNowAssetAuction([])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: authorityAuctionPDA,
+                collection: null,
+                asset: assetPubkey,
+                owner: signer.publicKey,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+            })
+            .signers([buyer])
+            .rpc();
+
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+
+        const authorityAuction = await program.account.collectionAuction.fetch(authorityAuctionPDA);
+        assert(authorityAuction.openAuctions == 0);
+    });
+
+    it("update authority sets the allowlist of a standalone asset auction", async () => {
+        const assetPubkey = await createStandaloneAsset(studio.publicKey);
+        await createAssetAuction(assetPubkey, null);
+
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [authorityAuctionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const root = Array(32).fill(1);
+        await program.methods
+            .setAuctionAllowlist(root)
+            .accountsPartial({
+                authority: studio.publicKey,
+                config: auctionConfigPDA,
+                collection: null,
+                collectionAuction: authorityAuctionPDA,
+                assetAuction: assetAuctionPDA,
+            })
+            .signers([studio])
+            .rpc();
+
+        const assetAuction = await program.account.assetAuction.fetch(assetAuctionPDA);
+        assert.deepEqual(assetAuction.allowlistRoot, root);
+    });
+
+    it("cancel a standalone asset auction", async () => {
+        const assetPubkey = await createStandaloneAsset(studio.publicKey);
+        await createAssetAuction(assetPubkey, null);
+
+        await program.methods
+            .cancelAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: authorityAuctionPDA,
+                collection: null,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.owner.toString() == payerWallet.publicKey.toBase58());
+        assert(asset.transferDelegate == undefined, "Transfer delegate exists after auction cancel")
+        assert(asset.freezeDelegate == undefined, "Freeze delegate exists after auction cancel")
+    });
+
+    it("try list an asset of another update authority raises", async () => {
+        const assetPubkey = await createStandaloneAsset(anchor.web3.Keypair.generate().publicKey);
+
+        let failed = false;
+        try {
+            await createAssetAuction(assetPubkey, null);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "ConstraintRaw")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try list a standalone asset with a collection account raises", async () => {
+        const assetPubkey = await createStandaloneAsset(studio.publicKey);
+
+        let failed = false;
+        try {
+            await createAssetAuction(assetPubkey, collectionPubkey);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidCollection")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
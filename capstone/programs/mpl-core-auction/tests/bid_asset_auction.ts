@@ -42,6 +42,8 @@ describe("Asset auction bidding", () => {
         feeBPS: 100,
         minDurationMinutes: 0,
         maxDurationMinutes: 14400,
+        extension: {windowMinutes: 1, extensionMinutes: 1, maxExtensions: 1},
+        minBidIncrement: {bps: {bps: 1000}},
     };
 
     // config account pda
@@ -113,7 +115,9 @@ describe("Asset auction bidding", () => {
                 initParams.seed,
                 initParams.feeBPS,
                 initParams.minDurationMinutes,
-                initParams.maxDurationMinutes
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
             )
             .rpc(); 
     });
@@ -129,7 +133,7 @@ describe("Asset auction bidding", () => {
     before("create collection auction", async () => {
         // whitelist collection
         await program.methods
-            .createCollectionAuction()
+            .createCollectionAuction(null)
             .accountsPartial({config: auctionConfigPDA})
             .accounts({collection: collectionPubkey})
             .rpc();
@@ -148,8 +152,8 @@ describe("Asset auction bidding", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -160,14 +164,15 @@ describe("Asset auction bidding", () => {
         const buyer = await createSigner()
         const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid)
+            .bidAssetAuction(buyerBid, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer.publicKey,
                 payer: buyer.publicKey,
+                previousBidRefund: null,
             })
             .accounts({
-                previousBuyer: payerWallet.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
@@ -187,6 +192,9 @@ describe("Asset auction bidding", () => {
         assert(assetAuctionAccount.buyer.toBase58() === buyer.publicKey.toBase58());
         assert(assetAuctionAccount.buyerBidLamports.eq(buyerBid));
         assert(assetAuctionAccount.firstBidTimestamp.gt(new BN(0)));
+        assert(assetAuctionAccount.endTimestamp.eq(
+            assetAuctionAccount.firstBidTimestamp.add(new BN(createAssetAuctionArgs.durationMinutes * 60))
+        ));
     });
 
     it("bid raising", async () => {
@@ -202,8 +210,8 @@ describe("Asset auction bidding", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -218,14 +226,15 @@ describe("Asset auction bidding", () => {
         const buyer = await createSigner()
         const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid)
+            .bidAssetAuction(buyerBid, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer.publicKey,
                 payer: buyer.publicKey,
+                previousBidRefund: null,
             })
             .accounts({
-                previousBuyer: payerWallet.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
@@ -244,31 +253,32 @@ describe("Asset auction bidding", () => {
         const buyer2 = await createSigner()
         const buyerBid2 = new BN(3 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid2)
+            .bidAssetAuction(buyerBid2, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer2.publicKey,
                 payer: buyer2.publicKey,
             })
             .accounts({
-                previousBuyer: buyer.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
             .signers([buyer2])
             .rpc();
 
-        // fetch first buyer lamports after second bid
+        // first buyer is not paid back, its bid is credited to a refund account
         buyerAccountInfo = await program.provider.connection.getAccountInfo(buyer.publicKey)
-        assert(buyerAccountInfo?.lamports == anchor.web3.LAMPORTS_PER_SOL * 10)
+        assert(buyerAccountInfo?.lamports == anchor.web3.LAMPORTS_PER_SOL * 8)
 
-        // fetch second buyer lamports after second bid
+        // fetch second buyer lamports after second bid, it pays the refund account rent
+        const refundRent = await program.provider.connection.getMinimumBalanceForRentExemption(program.account.bidRefund.size)
         let buyer2AccountInfo = await program.provider.connection.getAccountInfo(buyer2.publicKey)
-        assert(buyer2AccountInfo?.lamports == anchor.web3.LAMPORTS_PER_SOL * 7)
+        assert(buyer2AccountInfo?.lamports == anchor.web3.LAMPORTS_PER_SOL * 7 - refundRent)
 
-        // fetch vault lamports after second bid
+        // fetch vault lamports after second bid, outbid funds stay in the vault
         vaultAccountInfo = await program.provider.connection.getAccountInfo(vaultPDA)
-        assert(vaultAccountInfo?.lamports == vault_starting_lamports + buyerBid2.toNumber())
+        assert(vaultAccountInfo?.lamports == vault_starting_lamports + buyerBid.toNumber() + buyerBid2.toNumber())
         
         // Get asset auction PDA
         const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
@@ -276,6 +286,16 @@ describe("Asset auction bidding", () => {
             program.programId,
         );
 
+        // fetch first buyer refund account
+        const [bidRefundPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("refund"), assetAuctionPDA.toBuffer(), buyer.publicKey.toBuffer(), buyer.publicKey.toBuffer()],
+            program.programId,
+        );
+        const bidRefundAccount = await program.account.bidRefund.fetch(bidRefundPDA);
+        assert(bidRefundAccount.bidder.toBase58() === buyer.publicKey.toBase58());
+        assert(bidRefundAccount.funder.toBase58() === buyer.publicKey.toBase58());
+        assert(bidRefundAccount.lamports.eq(buyerBid));
+
         // fetch asset auction account
         const assetAuctionAccount = await program.account.assetAuction.fetch(assetAuctionPDA);
         assert(assetAuctionAccount.buyer.toBase58() === buyer2.publicKey.toBase58());
@@ -283,6 +303,68 @@ describe("Asset auction bidding", () => {
         assert(assetAuctionAccount.firstBidTimestamp.gt(new BN(0)));
     });
 
+    it("bid close to the end extends auction", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction args
+        const createAssetAuctionArgs = {
+            durationMinutes: 1,
+            minBid: new BN(100),
+        }
+        
+        // create asset auction
+        await program.methods
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // Get asset auction PDA
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+
+        // place bids, all of them inside the extension window
+        const buyers = [await createSigner(), await createSigner(), await createSigner()]
+        let previousBidRefund: anchor.web3.PublicKey | null = null
+        for (const [i, buyer] of buyers.entries()) {
+            await program.methods
+                .bidAssetAuction(new BN((i + 1) * anchor.web3.LAMPORTS_PER_SOL), [])
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    buyer: buyer.publicKey,
+                    payer: buyer.publicKey,
+                    previousBidRefund: previousBidRefund,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .signers([buyer])
+                .rpc();
+            previousBidRefund = anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("refund"), assetAuctionPDA.toBuffer(), buyer.publicKey.toBuffer(), buyer.publicKey.toBuffer()],
+                program.programId,
+            )[0]
+        }
+
+        // only one extension is allowed by config
+        const assetAuctionAccount = await program.account.assetAuction.fetch(assetAuctionPDA);
+        const durationSeconds = createAssetAuctionArgs.durationMinutes * 60
+        const extensionSeconds = initParams.extension.extensionMinutes * 60
+        assert(assetAuctionAccount.extensions === initParams.extension.maxExtensions);
+        assert(assetAuctionAccount.endTimestamp.eq(
+            assetAuctionAccount.firstBidTimestamp.add(new BN(durationSeconds + extensionSeconds))
+        ));
+    });
+
     it("try lower bid raises", async () => {
         // create asset 
         let asset = await createAsset()
@@ -296,8 +378,8 @@ describe("Asset auction bidding", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -308,14 +390,15 @@ describe("Asset auction bidding", () => {
         const buyer = await createSigner()
         const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid)
+            .bidAssetAuction(buyerBid, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer.publicKey,
                 payer: buyer.publicKey,
+                previousBidRefund: null,
             })
             .accounts({
-                previousBuyer: payerWallet.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
@@ -329,14 +412,14 @@ describe("Asset auction bidding", () => {
         let failed = false;
         try {
             await program.methods
-                .bidAssetAuction(buyerBid2)
+                .bidAssetAuction(buyerBid2, [])
                 .accountsPartial({
                     config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
                     buyer: buyer2.publicKey,
                     payer: buyer2.publicKey,
                 })
                 .accounts({
-                    previousBuyer: buyer.publicKey,
                     collection: collectionPubkey,
                     asset: assetPubkey,
                 })
@@ -352,6 +435,126 @@ describe("Asset auction bidding", () => {
         }
     });
 
+    it("try opening bid below min bid raises", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction args
+        const createAssetAuctionArgs = {
+            durationMinutes: 1,
+            minBid: new BN(anchor.web3.LAMPORTS_PER_SOL),
+        }
+        
+        // create asset auction
+        await program.methods
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // should fail because of bid below min bid
+        const buyer = await createSigner()
+        const buyerBid = createAssetAuctionArgs.minBid.subn(1)
+        let failed = false;
+        try {
+            await program.methods
+                .bidAssetAuction(buyerBid, [])
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    buyer: buyer.publicKey,
+                    payer: buyer.publicKey,
+                    previousBidRefund: null,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .signers([buyer])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "BidBelowMinimum")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try bid below min increment raises", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction args
+        const createAssetAuctionArgs = {
+            durationMinutes: 1,
+            minBid: new BN(100),
+        }
+        
+        // create asset auction
+        await program.methods
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // create first bid
+        const buyer = await createSigner()
+        const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        await program.methods
+            .bidAssetAuction(buyerBid, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // should fail because second bid is higher but below the 10% increment
+        const buyer2 = await createSigner()
+        const buyerBid2 = buyerBid.muln(11).divn(10).subn(1)
+        let failed = false;
+        try {
+            await program.methods
+                .bidAssetAuction(buyerBid2, [])
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    buyer: buyer2.publicKey,
+                    payer: buyer2.publicKey,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .signers([buyer2])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "BidIncrementTooLow")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
     it("try owner bid raises", async () => {
         // create asset 
         let asset = await createAsset()
@@ -365,8 +568,8 @@ describe("Asset auction bidding", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -377,14 +580,15 @@ describe("Asset auction bidding", () => {
         const buyer = await createSigner()
         const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid)
+            .bidAssetAuction(buyerBid, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer.publicKey,
                 payer: buyer.publicKey,
+                previousBidRefund: null,
             })
             .accounts({
-                previousBuyer: payerWallet.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
@@ -397,14 +601,14 @@ describe("Asset auction bidding", () => {
         let failed = false;
         try {
             await program.methods
-                .bidAssetAuction(ownerBid)
+                .bidAssetAuction(ownerBid, [])
                 .accountsPartial({
                     config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
                     buyer: signer.publicKey,
                     payer: signer.publicKey,
                 })
                 .accounts({
-                    previousBuyer: buyer.publicKey,
                     collection: collectionPubkey,
                     asset: assetPubkey,
                 })
@@ -432,8 +636,8 @@ describe("Asset auction bidding", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -444,14 +648,15 @@ describe("Asset auction bidding", () => {
         const buyer = await createSigner()
         const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid)
+            .bidAssetAuction(buyerBid, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer.publicKey,
                 payer: buyer.publicKey,
+                previousBidRefund: null,
             })
             .accounts({
-                previousBuyer: payerWallet.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
@@ -464,14 +669,14 @@ describe("Asset auction bidding", () => {
         let failed = false;
         try {
             await program.methods
-                .bidAssetAuction(buyerBid2)
+                .bidAssetAuction(buyerBid2, [])
                 .accountsPartial({
                     config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
                     buyer: buyer2.publicKey,
                     payer: buyer2.publicKey,
                 })
                 .accounts({
-                    previousBuyer: buyer.publicKey,
                     collection: collectionPubkey,
                     asset: assetPubkey,
                 })
@@ -0,0 +1,290 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Bid history", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 20,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to create an english asset auction, returns its bid history pda
+    async function createAssetAuction(assetPubkey: anchor.web3.PublicKey, durationMinutes: number): Promise<anchor.web3.PublicKey> {
+        await program.methods
+            .createAssetAuction(durationMinutes, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        const [assetAuctionPDA, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const [bidHistoryPDA, _6] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("bid_history"), assetAuctionPDA.toBuffer()],
+            program.programId,
+        );
+        return bidHistoryPDA;
+    }
+
+    // helper function to bid on an asset auction
+    async function bid(assetPubkey: anchor.web3.PublicKey, buyer: anchor.web3.Keypair, lamports: BN, first: boolean) {
+        await program.methods
+            .bidAssetAuction(lamports, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                ...(first ? {previousBidRefund: null} : {}),
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+    }
+
+    it("bids are appended to the history", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const bidHistoryPDA = await createAssetAuction(assetPubkey, 60);
+
+        // history starts empty
+        let bidHistory = await program.account.bidHistory.fetch(bidHistoryPDA);
+        assert(bidHistory.totalBids == 0);
+        assert(bidHistory.bids.length == 0);
+
+        // three bids from two bidders
+        const firstBuyer = await createSigner();
+        const secondBuyer = await createSigner();
+        const bids: [anchor.web3.Keypair, BN][] = [
+            [firstBuyer, new BN(LAMPORTS_PER_SOL)],
+            [secondBuyer, new BN(2 * LAMPORTS_PER_SOL)],
+            [firstBuyer, new BN(3 * LAMPORTS_PER_SOL)],
+        ];
+        for (const [index, [buyer, lamports]] of bids.entries()) {
+            await bid(assetPubkey, buyer, lamports, index == 0);
+        }
+
+        // history keeps bids in order
+        bidHistory = await program.account.bidHistory.fetch(bidHistoryPDA);
+        assert(bidHistory.totalBids == 3);
+        assert(bidHistory.bids.length == 3);
+        for (const [index, [buyer, lamports]] of bids.entries()) {
+            assert(bidHistory.bids[index].bidder.equals(buyer.publicKey));
+            assert(bidHistory.bids[index].lamports.eq(lamports));
+            assert(bidHistory.bids[index].timestamp.gtn(0));
+        }
+    });
+
+    it("history is closed when the auction is cancelled", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const bidHistoryPDA = await createAssetAuction(assetPubkey, 60);
+
+        await program.methods
+            .cancelAssetAuction()
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        const bidHistoryAccountInfo = await provider.connection.getAccountInfo(bidHistoryPDA);
+        assert(bidHistoryAccountInfo == null);
+    });
+
+    it("cancel needs the history of an english auction", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await createAssetAuction(assetPubkey, 60);
+
+        let failed = false;
+        try {
+            await program.methods
+                .cancelAssetAuction()
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA, bidHistory: null})
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "MissingBidHistory")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("history is closed when the auction is completed", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const bidHistoryPDA = await createAssetAuction(assetPubkey, 0);
+
+        const buyer = await createSigner();
+        await bid(assetPubkey, buyer, new BN(LAMPORTS_PER_SOL), true);
+
+        await program.methods
+            .completeAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                buyer: buyer.publicKey,
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        const bidHistoryAccountInfo = await provider.connection.getAccountInfo(bidHistoryPDA);
+        assert(bidHistoryAccountInfo == null);
+    });
+});
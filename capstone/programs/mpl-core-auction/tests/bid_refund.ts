@@ -0,0 +1,334 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Bid refund", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 17,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+
+    // helper function to create an asset auction
+    async function createAssetAuction(assetPubkey: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> {
+        await program.methods
+            .createAssetAuction(60, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        return assetAuctionPDA
+    }
+
+    // helper function to get the refund account of a bidder and its funder
+    function getBidRefundPDA(assetAuctionPDA: anchor.web3.PublicKey, bidder: anchor.web3.PublicKey, funder: anchor.web3.PublicKey): anchor.web3.PublicKey {
+        const [bidRefundPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("refund"), assetAuctionPDA.toBuffer(), bidder.toBuffer(), funder.toBuffer()],
+            program.programId,
+        );
+        return bidRefundPDA
+    }
+
+    // helper function to bid on behalf of a bidder, the opening bid has no refund account
+    async function bidAssetAuction(assetPubkey: anchor.web3.PublicKey, bidder: anchor.web3.PublicKey, funder: anchor.web3.Keypair, lamports: BN, previousBidRefund: anchor.web3.PublicKey | null) {
+        await program.methods
+            .bidAssetAuction(lamports, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: bidder,
+                payer: funder.publicKey,
+                previousBidRefund: previousBidRefund,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([funder])
+            .rpc();
+    }
+
+    it("bidder withdraws refund to funder", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        // funder pays the bid of another bidder
+        const bidder = await createSigner();
+        const funder = await createSigner();
+        const bid = new BN(2 * LAMPORTS_PER_SOL);
+        await bidAssetAuction(assetPubkey, bidder.publicKey, funder, bid, null);
+
+        // outbid, the new bid does not need the previous bidder accounts
+        const bidRefundPDA = getBidRefundPDA(assetAuctionPDA, bidder.publicKey, funder.publicKey);
+        const other = await createSigner();
+        await bidAssetAuction(assetPubkey, other.publicKey, other, new BN(3 * LAMPORTS_PER_SOL), bidRefundPDA);
+
+        const bidRefundAccount = await program.account.bidRefund.fetch(bidRefundPDA);
+        assert(bidRefundAccount.bidder.toBase58() === bidder.publicKey.toBase58());
+        assert(bidRefundAccount.funder.toBase58() === funder.publicKey.toBase58());
+        assert(bidRefundAccount.lamports.eq(bid));
+
+        assert(bidRefundAccount.rentPayer.toBase58() === other.publicKey.toBase58());
+
+        // bidder withdraws, funds go back to the funder and rent to the outbidding bidder that paid it
+        const refundRent = await provider.connection.getMinimumBalanceForRentExemption(program.account.bidRefund.size);
+        const otherLamports = await provider.connection.getBalance(other.publicKey);
+        await program.methods
+            .withdrawRefund()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                authority: bidder.publicKey,
+                funder: funder.publicKey,
+                rentPayer: other.publicKey,
+                bidRefund: bidRefundPDA,
+            })
+            .signers([bidder])
+            .rpc();
+
+        assert(await provider.connection.getBalance(funder.publicKey) === 10 * LAMPORTS_PER_SOL);
+        assert(await provider.connection.getBalance(bidder.publicKey) === 10 * LAMPORTS_PER_SOL);
+        assert(await provider.connection.getBalance(other.publicKey) === otherLamports + refundRent);
+        assert(await provider.connection.getAccountInfo(bidRefundPDA) === null);
+    });
+
+    it("outbid bids accumulate in the refund account", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        const bidder = await createSigner();
+        const other = await createSigner();
+        const bidderRefundPDA = getBidRefundPDA(assetAuctionPDA, bidder.publicKey, bidder.publicKey);
+        const otherRefundPDA = getBidRefundPDA(assetAuctionPDA, other.publicKey, other.publicKey);
+        await bidAssetAuction(assetPubkey, bidder.publicKey, bidder, new BN(1 * LAMPORTS_PER_SOL), null);
+        await bidAssetAuction(assetPubkey, other.publicKey, other, new BN(2 * LAMPORTS_PER_SOL), bidderRefundPDA);
+        await bidAssetAuction(assetPubkey, bidder.publicKey, bidder, new BN(3 * LAMPORTS_PER_SOL), otherRefundPDA);
+        await bidAssetAuction(assetPubkey, other.publicKey, other, new BN(4 * LAMPORTS_PER_SOL), bidderRefundPDA);
+
+        const bidRefundAccount = await program.account.bidRefund.fetch(bidderRefundPDA);
+        assert(bidRefundAccount.lamports.eq(new BN(4 * LAMPORTS_PER_SOL)));
+    });
+
+    it("refunds of a bidder paid by different funders are kept apart", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        // bidder is funded by two funders, each outbid bid is credited to its own funder
+        const bidder = await createSigner();
+        const funder = await createSigner();
+        const other = await createSigner();
+        const bidderRefundPDA = getBidRefundPDA(assetAuctionPDA, bidder.publicKey, bidder.publicKey);
+        const funderRefundPDA = getBidRefundPDA(assetAuctionPDA, bidder.publicKey, funder.publicKey);
+        const otherRefundPDA = getBidRefundPDA(assetAuctionPDA, other.publicKey, other.publicKey);
+        await bidAssetAuction(assetPubkey, bidder.publicKey, bidder, new BN(1 * LAMPORTS_PER_SOL), null);
+        await bidAssetAuction(assetPubkey, other.publicKey, other, new BN(2 * LAMPORTS_PER_SOL), bidderRefundPDA);
+        await bidAssetAuction(assetPubkey, bidder.publicKey, funder, new BN(3 * LAMPORTS_PER_SOL), otherRefundPDA);
+        await bidAssetAuction(assetPubkey, other.publicKey, other, new BN(4 * LAMPORTS_PER_SOL), funderRefundPDA);
+
+        const bidderRefundAccount = await program.account.bidRefund.fetch(bidderRefundPDA);
+        assert(bidderRefundAccount.funder.toBase58() === bidder.publicKey.toBase58());
+        assert(bidderRefundAccount.lamports.eq(new BN(1 * LAMPORTS_PER_SOL)));
+        const funderRefundAccount = await program.account.bidRefund.fetch(funderRefundPDA);
+        assert(funderRefundAccount.funder.toBase58() === funder.publicKey.toBase58());
+        assert(funderRefundAccount.lamports.eq(new BN(3 * LAMPORTS_PER_SOL)));
+    });
+
+    it("try withdraw refund with other signer raises", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        const bidder = await createSigner();
+        const bidRefundPDA = getBidRefundPDA(assetAuctionPDA, bidder.publicKey, bidder.publicKey);
+        await bidAssetAuction(assetPubkey, bidder.publicKey, bidder, new BN(1 * LAMPORTS_PER_SOL), null);
+        const other = await createSigner();
+        await bidAssetAuction(assetPubkey, other.publicKey, other, new BN(2 * LAMPORTS_PER_SOL), bidRefundPDA);
+
+        let failed = false;
+        try {
+            await program.methods
+                .withdrawRefund()
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    authority: other.publicKey,
+                    funder: bidder.publicKey,
+                    rentPayer: other.publicKey,
+                    bidRefund: bidRefundPDA,
+                })
+                .signers([other])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidRefundAuthority")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try opening bid with refund account raises", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createAssetAuction(assetPubkey);
+
+        const bidder = await createSigner();
+        let failed = false;
+        try {
+            await bidAssetAuction(assetPubkey, bidder.publicKey, bidder, new BN(1 * LAMPORTS_PER_SOL), getBidRefundPDA(assetAuctionPDA, payerWallet.publicKey, payerWallet.publicKey));
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidBidRefund")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
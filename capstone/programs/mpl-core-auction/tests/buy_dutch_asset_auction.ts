@@ -0,0 +1,282 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Dutch asset auction buy", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 8,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to list an asset in a dutch auction
+    async function createDutchAssetAuction(startPrice: BN, floorPrice: BN): Promise<anchor.web3.PublicKey> {
+        // create asset 
+        const asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create dutch asset auction
+        await program.methods
+            .createDutchAssetAuction(60, startPrice, floorPrice, {linear: {}})
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        return assetPubkey
+    }
+
+    it("buy dutch asset auction", async () => {
+        // create dutch asset auction
+        const startPrice = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        const floorPrice = new BN(anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createDutchAssetAuction(startPrice, floorPrice)
+
+        // fetch tresuary lamports before buy
+        const tresuaryStartingLamports = (await program.provider.connection.getAccountInfo(tresuaryPDA))?.lamports ?? 0
+
+        // buy at current price
+        const buyer = await createSigner()
+        await program.methods
+            .buyDutchAssetAuction(startPrice)
+            .accountsPartial({
+                config: auctionConfigPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // check asset
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+
+        // price paid is between floor and start price
+        const buyerAccountInfo = await program.provider.connection.getAccountInfo(buyer.publicKey)
+        const paid = new BN(10 * anchor.web3.LAMPORTS_PER_SOL - (buyerAccountInfo?.lamports ?? 0))
+        assert(paid.lte(startPrice) && paid.gt(floorPrice))
+
+        // tresuary receives the fee
+        const tresuaryAccountInfo = await program.provider.connection.getAccountInfo(tresuaryPDA)
+        assert(tresuaryAccountInfo?.lamports == tresuaryStartingLamports + Math.ceil(paid.toNumber() * 0.01))
+
+        // asset auction is closed
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        assert(await program.provider.connection.getAccountInfo(assetAuctionPDA) == null)
+    });
+
+    it("try buy above max price raises", async () => {
+        // create dutch asset auction
+        const startPrice = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        const floorPrice = new BN(anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createDutchAssetAuction(startPrice, floorPrice)
+
+        // should fail because price has not decayed to the floor
+        const buyer = await createSigner()
+        let failed = false;
+        try {
+            await program.methods
+                .buyDutchAssetAuction(floorPrice)
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    buyer: buyer.publicKey,
+                    payer: buyer.publicKey,
+                    owner: signer.publicKey,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .signers([buyer])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "PriceAboveMax")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try bid on dutch asset auction raises", async () => {
+        // create dutch asset auction
+        const startPrice = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        const floorPrice = new BN(anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createDutchAssetAuction(startPrice, floorPrice)
+
+        // should fail because dutch auctions do not take bids
+        const buyer = await createSigner()
+        let failed = false;
+        try {
+            await program.methods
+                .bidAssetAuction(startPrice, [])
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    buyer: buyer.publicKey,
+                    payer: buyer.publicKey,
+                    previousBidRefund: null,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .signers([buyer])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidAuctionKind")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
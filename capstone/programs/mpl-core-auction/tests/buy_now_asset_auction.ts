@@ -0,0 +1,360 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Asset auction buy now", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 10,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to list an asset with buy now
+    async function createBuyNowAssetAuction(buyNow: BN, buyNowCutoff: BN | null): Promise<anchor.web3.PublicKey> {
+        // create asset 
+        const asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction
+        await program.methods
+            .createAssetAuction(60, new BN(100), buyNow, buyNowCutoff, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        return assetPubkey
+    }
+
+    it("buy now asset auction", async () => {
+        // create asset auction
+        const buyNow = new BN(5 * anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createBuyNowAssetAuction(buyNow, null)
+
+        // fetch tresuary lamports before buy
+        const tresuaryStartingLamports = (await program.provider.connection.getAccountInfo(tresuaryPDA))?.lamports ?? 0
+
+        // buy now
+        const buyer = await createSigner()
+        await program.methods
+            .buyNowAssetAuction([])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // check asset
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+
+        // check balances
+        const buyerAccountInfo = await program.provider.connection.getAccountInfo(buyer.publicKey)
+        assert(buyerAccountInfo?.lamports == 10 * anchor.web3.LAMPORTS_PER_SOL - buyNow.toNumber())
+        const tresuaryAccountInfo = await program.provider.connection.getAccountInfo(tresuaryPDA)
+        assert(tresuaryAccountInfo?.lamports == tresuaryStartingLamports + Math.ceil(buyNow.toNumber() * 0.01))
+    });
+
+    it("buy now credits current bidder refund", async () => {
+        // create asset auction
+        const buyNow = new BN(5 * anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createBuyNowAssetAuction(buyNow, null)
+
+        // create first bid
+        const bidder = await createSigner()
+        const bid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        await program.methods
+            .bidAssetAuction(bid, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: bidder.publicKey,
+                payer: bidder.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([bidder])
+            .rpc();
+
+        // buy now
+        const buyer = await createSigner()
+        await program.methods
+            .buyNowAssetAuction([])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // check asset and bidder refund account
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const [bidRefundPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("refund"), assetAuctionPDA.toBuffer(), bidder.publicKey.toBuffer(), bidder.publicKey.toBuffer()],
+            program.programId,
+        );
+        const bidRefundAccount = await program.account.bidRefund.fetch(bidRefundPDA);
+        assert(bidRefundAccount.funder.toBase58() === bidder.publicKey.toBase58());
+        assert(bidRefundAccount.lamports.eq(bid));
+
+        // bidder withdraws the refund, the refund account rent goes back to the buyer that paid it
+        const refundRent = await program.provider.connection.getMinimumBalanceForRentExemption(program.account.bidRefund.size)
+        const buyerLamports = (await program.provider.connection.getAccountInfo(buyer.publicKey))!.lamports
+        await program.methods
+            .withdrawRefund()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                authority: bidder.publicKey,
+                funder: bidder.publicKey,
+                rentPayer: buyer.publicKey,
+                bidRefund: bidRefundPDA,
+            })
+            .signers([bidder])
+            .rpc();
+        const bidderAccountInfo = await program.provider.connection.getAccountInfo(bidder.publicKey)
+        assert(bidderAccountInfo?.lamports == 10 * anchor.web3.LAMPORTS_PER_SOL)
+        const buyerAccountInfo = await program.provider.connection.getAccountInfo(buyer.publicKey)
+        assert(buyerAccountInfo?.lamports == buyerLamports + refundRent)
+    });
+
+    it("try buy now after cutoff raises", async () => {
+        // create asset auction
+        const buyNow = new BN(5 * anchor.web3.LAMPORTS_PER_SOL)
+        const buyNowCutoff = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createBuyNowAssetAuction(buyNow, buyNowCutoff)
+
+        // bid reaching the cutoff
+        const bidder = await createSigner()
+        await program.methods
+            .bidAssetAuction(buyNowCutoff, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: bidder.publicKey,
+                payer: bidder.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([bidder])
+            .rpc();
+
+        // should fail because bidding passed the cutoff
+        const buyer = await createSigner()
+        let failed = false;
+        try {
+            await program.methods
+                .buyNowAssetAuction([])
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    buyer: buyer.publicKey,
+                    payer: buyer.publicKey,
+                    owner: signer.publicKey,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .signers([buyer])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "BuyNowUnavailable")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try create asset auction with buy now below min bid raises", async () => {
+        // create asset 
+        const asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // should fail because buy now is lower than the min bid
+        let failed = false;
+        try {
+            await program.methods
+                .createAssetAuction(60, new BN(anchor.web3.LAMPORTS_PER_SOL), new BN(100), null, null)
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidBuyNowPrice")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
@@ -42,6 +42,8 @@ describe("Asset auction cancel", () => {
         feeBPS: 100,
         minDurationMinutes: 0,
         maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
     };
 
     // config account pda
@@ -101,7 +103,9 @@ describe("Asset auction cancel", () => {
                 initParams.seed,
                 initParams.feeBPS,
                 initParams.minDurationMinutes,
-                initParams.maxDurationMinutes
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
             )
             .rpc(); 
     });
@@ -117,7 +121,7 @@ describe("Asset auction cancel", () => {
     before("create collection auction", async () => {
         // whitelist collection
         await program.methods
-            .createCollectionAuction()
+            .createCollectionAuction(null)
             .accountsPartial({config: auctionConfigPDA})
             .accounts({collection: collectionPubkey})
             .rpc();
@@ -136,8 +140,8 @@ describe("Asset auction cancel", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -152,7 +156,7 @@ describe("Asset auction cancel", () => {
         // Cancel asset auction
         await program.methods
             .cancelAssetAuction()
-            .accountsPartial({config: auctionConfigPDA})
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -178,8 +182,8 @@ describe("Asset auction cancel", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -190,14 +194,15 @@ describe("Asset auction cancel", () => {
         const buyer = await createSigner()
         const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid)
+            .bidAssetAuction(buyerBid, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer.publicKey,
                 payer: buyer.publicKey,
+                previousBidRefund: null,
             })
             .accounts({
-                previousBuyer: payerWallet.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
@@ -209,7 +214,7 @@ describe("Asset auction cancel", () => {
         try {
             await program.methods
                 .cancelAssetAuction()
-                .accountsPartial({config: auctionConfigPDA})
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
                 .accounts({
                     collection: collectionPubkey,
                     asset: assetPubkey,
@@ -0,0 +1,318 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Collection auction pause and removal", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 13,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to create an english asset auction
+    async function createAssetAuction(assetPubkey: anchor.web3.PublicKey) {
+        await program.methods
+            .createAssetAuction(60, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    // helper function to cancel an asset auction
+    async function cancelAssetAuction(assetPubkey: anchor.web3.PublicKey) {
+        await program.methods
+            .cancelAssetAuction()
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    it("pause and resume collection", async () => {
+        await program.methods
+            .pauseCollection()
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .rpc();
+
+        let collection_auction = await program.account.collectionAuction.fetch(auctionCollectionPDA);
+        assert(!collection_auction.enabled);
+
+        // should fail because collection is paused
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        let failed = false;
+        try {
+            await createAssetAuction(assetPubkey);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "CollectionPaused")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        await program.methods
+            .resumeCollection()
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .rpc();
+
+        // create works again and is counted
+        await createAssetAuction(assetPubkey);
+        collection_auction = await program.account.collectionAuction.fetch(auctionCollectionPDA);
+        assert(collection_auction.enabled);
+        assert(collection_auction.openAuctions === 1);
+
+        await cancelAssetAuction(assetPubkey);
+        collection_auction = await program.account.collectionAuction.fetch(auctionCollectionPDA);
+        assert(collection_auction.openAuctions === 0);
+    });
+
+    it("paused collection blocks bids and allows cancel", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await createAssetAuction(assetPubkey);
+
+        await program.methods
+            .pauseCollection()
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .rpc();
+
+        // should fail because collection is paused
+        const buyer = await createSigner();
+        let failed = false;
+        try {
+            await program.methods
+                .bidAssetAuction(new BN(anchor.web3.LAMPORTS_PER_SOL), [])
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    buyer: buyer.publicKey,
+                    payer: buyer.publicKey,
+                    previousBidRefund: null,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .signers([buyer])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "CollectionPaused")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        // cancel still works on paused collections
+        await cancelAssetAuction(assetPubkey);
+
+        await program.methods
+            .resumeCollection()
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .rpc();
+    });
+
+    it("try pause collection with non admin raises", async () => {
+        const other = await createSigner();
+
+        let failed = false;
+        try {
+            await program.methods
+                .pauseCollection()
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA, admin: other.publicKey})
+                .signers([other])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidAdmin")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("remove collection auction", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await createAssetAuction(assetPubkey);
+
+        // should fail because an auction is still open
+        let failed = false;
+        try {
+            await program.methods
+                .removeCollectionAuction()
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "CollectionHasOpenAuctions")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        // remove once the auction is cancelled
+        await cancelAssetAuction(assetPubkey);
+        await program.methods
+            .removeCollectionAuction()
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .rpc();
+
+        const collectionAuctionAccountInfo = await provider.connection.getAccountInfo(auctionCollectionPDA);
+        assert(collectionAuctionAccountInfo == null);
+
+        // collection stats are closed with the collection auction
+        const [collectionStatsPDA, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("stats"), auctionCollectionPDA.toBuffer()],
+            program.programId,
+        );
+        const collectionStatsAccountInfo = await provider.connection.getAccountInfo(collectionStatsPDA);
+        assert(collectionStatsAccountInfo == null);
+    });
+});
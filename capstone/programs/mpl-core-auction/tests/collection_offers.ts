@@ -0,0 +1,260 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Collection offers", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 22,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to derive a collection offer pda
+    function collectionOfferPDA(bidder: anchor.web3.PublicKey): anchor.web3.PublicKey {
+        const [collectionOffer, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("collection_offer"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer(), bidder.toBuffer()],
+            program.programId,
+        );
+        return collectionOffer;
+    }
+
+    // helper function to make a collection offer expiring in an hour
+    async function makeCollectionOffer(bidder: anchor.web3.Keypair, lamports: BN, quantity: number) {
+        const expiryTimestamp = new BN(Math.floor(Date.now() / 1000) + 3600);
+        await program.methods
+            .makeCollectionOffer(lamports, quantity, expiryTimestamp)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({
+                bidder: bidder.publicKey,
+                collection: collectionPubkey,
+            })
+            .signers([bidder])
+            .rpc();
+    }
+
+    // helper function to fill one unit of a collection offer with an asset of the payer wallet
+    async function fillCollectionOffer(bidder: anchor.web3.PublicKey, assetPubkey: anchor.web3.PublicKey) {
+        await program.methods
+            .fillCollectionOffer()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                bidder: bidder,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    it("fill every unit of a collection offer", async () => {
+        const bidder = await createSigner();
+        const lamports = new BN(LAMPORTS_PER_SOL);
+        await makeCollectionOffer(bidder, lamports, 2);
+
+        let collectionOffer = await program.account.collectionOffer.fetch(collectionOfferPDA(bidder.publicKey));
+        assert(collectionOffer.collection.equals(collectionPubkey));
+        assert(collectionOffer.bidder.equals(bidder.publicKey));
+        assert(collectionOffer.lamports.eq(lamports));
+        assert(collectionOffer.quantity == 2);
+        assert(collectionOffer.filled == 0);
+
+        // first fill
+        let asset = await createAsset();
+        await fillCollectionOffer(bidder.publicKey, new anchor.web3.PublicKey(asset.publicKey.toString()));
+
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == bidder.publicKey.toBase58());
+        collectionOffer = await program.account.collectionOffer.fetch(collectionOfferPDA(bidder.publicKey));
+        assert(collectionOffer.filled == 1);
+
+        // last fill closes the collection offer
+        asset = await createAsset();
+        await fillCollectionOffer(bidder.publicKey, new anchor.web3.PublicKey(asset.publicKey.toString()));
+
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == bidder.publicKey.toBase58());
+        const collectionOfferAccountInfo = await provider.connection.getAccountInfo(collectionOfferPDA(bidder.publicKey));
+        assert(collectionOfferAccountInfo == null);
+    });
+
+    it("cancel refunds the remaining units", async () => {
+        const bidder = await createSigner();
+        const lamports = new BN(LAMPORTS_PER_SOL);
+        await makeCollectionOffer(bidder, lamports, 3);
+
+        // fill one unit
+        const asset = await createAsset();
+        await fillCollectionOffer(bidder.publicKey, new anchor.web3.PublicKey(asset.publicKey.toString()));
+
+        // cancel, two units are refunded
+        const bidderInitialLamports = (await provider.connection.getAccountInfo(bidder.publicKey))!.lamports;
+        await program.methods
+            .cancelCollectionOffer()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                bidder: bidder.publicKey,
+                collectionOffer: collectionOfferPDA(bidder.publicKey),
+            })
+            .signers([bidder])
+            .rpc();
+
+        const collectionOfferAccountInfo = await provider.connection.getAccountInfo(collectionOfferPDA(bidder.publicKey));
+        assert(collectionOfferAccountInfo == null);
+        const bidderLamports = (await provider.connection.getAccountInfo(bidder.publicKey))!.lamports;
+        assert(bidderLamports > bidderInitialLamports + 2 * LAMPORTS_PER_SOL);
+        assert(bidderLamports < bidderInitialLamports + 3 * LAMPORTS_PER_SOL);
+    });
+
+    it("invalid quantity", async () => {
+        const bidder = await createSigner();
+
+        let failed = false;
+        try {
+            await makeCollectionOffer(bidder, new BN(LAMPORTS_PER_SOL), 0);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidOffer")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
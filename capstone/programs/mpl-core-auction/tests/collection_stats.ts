@@ -0,0 +1,225 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Collection stats", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 19,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // collection stats pda
+    const [collectionStatsPDA, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("stats"), auctionCollectionPDA.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to list and sell an asset with buy now
+    async function sellAsset(priceLamports: BN): Promise<anchor.web3.PublicKey> {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction with a buy now price
+        await program.methods
+            .createAssetAuction(60, new BN(100), priceLamports, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // buy now
+        const buyer = await createSigner()
+        await program.methods
+            .buyNowAssetAuction([])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+
+        return assetPubkey;
+    }
+
+    it("stats are created with the collection auction", async () => {
+        const stats = await program.account.collectionStats.fetch(collectionStatsPDA);
+        assert(stats.collectionAuction.equals(auctionCollectionPDA));
+        assert(stats.sales.eqn(0));
+        assert(stats.volumeLamports.eqn(0));
+        assert(stats.recentSales.every((sale) => sale.priceLamports.eqn(0)));
+    });
+
+    it("sales are recorded", async () => {
+        const prices = [
+            new BN(2 * LAMPORTS_PER_SOL),
+            new BN(LAMPORTS_PER_SOL),
+            new BN(3 * LAMPORTS_PER_SOL),
+        ];
+        const assets = [];
+        for (const price of prices) {
+            assets.push(await sellAsset(price));
+        }
+
+        const stats = await program.account.collectionStats.fetch(collectionStatsPDA);
+        assert(stats.sales.eqn(3));
+        assert(stats.volumeLamports.eqn(6 * LAMPORTS_PER_SOL));
+        assert(stats.lastPriceLamports.eq(prices[2]));
+        assert(stats.lastSaleTimestamp.eq(stats.recentSales[2].timestamp));
+        assert(stats.windowMinLamports.eq(prices[1]));
+        assert(stats.windowMaxLamports.eq(prices[2]));
+
+        // ring buffer keeps sales in order of completion
+        for (const [index, price] of prices.entries()) {
+            assert(stats.recentSales[index].asset.equals(assets[index]));
+            assert(stats.recentSales[index].priceLamports.eq(price));
+        }
+        assert(stats.recentSales[3].priceLamports.eqn(0));
+    });
+});
@@ -8,11 +8,13 @@ import {
     create,
     fetchAsset,
     AssetV1,
+    ruleSet,
 } from '@metaplex-foundation/mpl-core'
 import { 
     createSignerFromKeypair,
     signerIdentity,
     generateSigner,
+    publicKey,
 } from '@metaplex-foundation/umi'
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
 import assert from "assert";
@@ -43,6 +45,8 @@ describe("Asset auction complete", () => {
         feeBPS: 100,
         minDurationMinutes: 0,
         maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
     };
 
     // config account pda
@@ -119,7 +123,9 @@ describe("Asset auction complete", () => {
                 initParams.seed,
                 initParams.feeBPS,
                 initParams.minDurationMinutes,
-                initParams.maxDurationMinutes
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
             )
             .rpc(); 
     });
@@ -135,7 +141,7 @@ describe("Asset auction complete", () => {
     before("create collection auction", async () => {
         // whitelist collection
         await program.methods
-            .createCollectionAuction()
+            .createCollectionAuction(null)
             .accountsPartial({config: auctionConfigPDA})
             .accounts({collection: collectionPubkey})
             .rpc();
@@ -154,8 +160,8 @@ describe("Asset auction complete", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -170,14 +176,15 @@ describe("Asset auction complete", () => {
         const buyer = await createSigner()
         const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid)
+            .bidAssetAuction(buyerBid, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer.publicKey,
                 payer: buyer.publicKey,
+                previousBidRefund: null,
             })
             .accounts({
-                previousBuyer: payerWallet.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
@@ -190,6 +197,7 @@ describe("Asset auction complete", () => {
             .completeAssetAuction()
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 owner: signer.publicKey,
             })
             .accounts({
@@ -238,8 +246,8 @@ describe("Asset auction complete", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -250,14 +258,15 @@ describe("Asset auction complete", () => {
         const buyer = await createSigner()
         const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
         await program.methods
-            .bidAssetAuction(buyerBid)
+            .bidAssetAuction(buyerBid, [])
             .accountsPartial({
                 config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
                 buyer: buyer.publicKey,
                 payer: buyer.publicKey,
+                previousBidRefund: null,
             })
             .accounts({
-                previousBuyer: payerWallet.publicKey,
                 collection: collectionPubkey,
                 asset: assetPubkey,
             })
@@ -271,6 +280,7 @@ describe("Asset auction complete", () => {
                 .completeAssetAuction()
                 .accountsPartial({
                     config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
                     owner: signer.publicKey,
                 })
                 .accounts({
@@ -302,8 +312,8 @@ describe("Asset auction complete", () => {
         
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -319,6 +329,7 @@ describe("Asset auction complete", () => {
                 .completeAssetAuction()
                 .accountsPartial({
                     config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
                     owner: signer.publicKey,
                 })
                 .accounts({
@@ -337,4 +348,145 @@ describe("Asset auction complete", () => {
         }
     });
 
+    // helper function to create asset with a royalties plugin
+    async function createRoyaltiesAsset(creator: anchor.web3.PublicKey, basisPoints: number): Promise<AssetV1> {
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        await create(umi, {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+            plugins: [{
+                type: 'Royalties',
+                basisPoints: basisPoints,
+                creators: [{address: publicKey(creator.toBase58()), percentage: 100}],
+                ruleSet: ruleSet('None'),
+            }],
+        }).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    it("complete auction pays creator royalties", async () => {
+        // create asset with 5% royalties
+        const creator = await createSigner();
+        let asset = await createRoyaltiesAsset(creator.publicKey, 500);
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction
+        await program.methods
+            .createAssetAuction(0, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // create first bid
+        const buyer = await createSigner()
+        const buyerBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        await program.methods
+            .bidAssetAuction(buyerBid, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // complete auction with creator as remaining account
+        await program.methods
+            .completeAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                buyer: buyer.publicKey,
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .remainingAccounts([{pubkey: creator.publicKey, isWritable: true, isSigner: false}])
+            .rpc();
+
+        // check asset
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+
+        // check creator balance after complete
+        const creatorAccountInfo = await program.provider.connection.getAccountInfo(creator.publicKey);
+        const royaltyExpectedBalance = buyerBid.toNumber() * 0.05;
+        assert(creatorAccountInfo?.lamports == anchor.web3.LAMPORTS_PER_SOL * 10 + royaltyExpectedBalance);
+    });
+
+    it("try complete with wrong creator accounts raises", async () => {
+        // create asset with 5% royalties
+        const creator = await createSigner();
+        const asset = await createRoyaltiesAsset(creator.publicKey, 500);
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction
+        await program.methods
+            .createAssetAuction(0, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // create first bid
+        const buyer = await createSigner()
+        await program.methods
+            .bidAssetAuction(new BN(anchor.web3.LAMPORTS_PER_SOL), [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // should fail because royalties would go to an account that is not the creator
+        let complete_failed = false;
+        try {
+            await program.methods
+                .completeAssetAuction()
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    owner: signer.publicKey,
+                })
+                .accounts({
+                    buyer: buyer.publicKey,
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .remainingAccounts([{pubkey: buyer.publicKey, isWritable: true, isSigner: false}])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidCreators")
+                complete_failed = true
+            }
+        } finally {
+            assert(complete_failed, "Auction was completed with wrong creators.")
+        }
+    });
+
 });
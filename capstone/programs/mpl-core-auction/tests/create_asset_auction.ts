@@ -60,6 +60,8 @@ describe("Asset auction creation", () => {
         feeBPS: 100,
         minDurationMinutes: 60,
         maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
     };
 
     // config account pda
@@ -106,7 +108,9 @@ describe("Asset auction creation", () => {
                 initParams.seed,
                 initParams.feeBPS,
                 initParams.minDurationMinutes,
-                initParams.maxDurationMinutes
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
             )
             .rpc(); 
     });
@@ -122,7 +126,7 @@ describe("Asset auction creation", () => {
     before("create collection auction", async () => {
         // whitelist collection
         await program.methods
-            .createCollectionAuction()
+            .createCollectionAuction(null)
             .accountsPartial({config: auctionConfigPDA})
             .accounts({collection: collectionPubkey})
             .rpc();
@@ -141,8 +145,8 @@ describe("Asset auction creation", () => {
 
         // Create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -163,11 +167,16 @@ describe("Asset auction creation", () => {
         // verify values
         assert(assetAuctionAccount.coreAsset.toBase58() === assetPubkey.toBase58());
         assert(assetAuctionAccount.owner.toBase58() === payerWallet.payer.publicKey.toBase58());
+        assert(assetAuctionAccount.kind.english != undefined);
         assert(assetAuctionAccount.durationMinutes === createAssetAuctionArgs.durationMinutes);
         assert(assetAuctionAccount.minBidLamports.eq(createAssetAuctionArgs.minBid));
         assert(assetAuctionAccount.buyer.toBase58() === signerPubkey.toBase58());
         assert(assetAuctionAccount.buyerBidLamports.eq(new BN(0)));
         assert(assetAuctionAccount.firstBidTimestamp.eq(new BN(0)));
+        assert(assetAuctionAccount.endTimestamp.eq(new BN(0)));
+        assert(assetAuctionAccount.extensions === 0);
+        assert(assetAuctionAccount.buyNowLamports === null);
+        assert(assetAuctionAccount.buyNowCutoffLamports === null);
         assert(assetAuctionAccount.bump === bump);
 
         // verify freeze plugin and asset state
@@ -239,8 +248,8 @@ describe("Asset auction creation", () => {
 
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -281,8 +290,8 @@ describe("Asset auction creation", () => {
         let short_failed = false;
         try {
             await program.methods
-                .createAssetAuction(createAssetAuctionShortArgs.durationMinutes, createAssetAuctionShortArgs.minBid)
-                .accountsPartial({config: auctionConfigPDA})
+                .createAssetAuction(createAssetAuctionShortArgs.durationMinutes, createAssetAuctionShortArgs.minBid, null, null, null)
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
                 .accounts({
                     collection: collectionPubkey,
                     asset: assetPubkey,
@@ -307,8 +316,8 @@ describe("Asset auction creation", () => {
         let long_failed = false;
         try {
             await program.methods
-                .createAssetAuction(createAssetAuctionLongArgs.durationMinutes, createAssetAuctionLongArgs.minBid)
-                .accountsPartial({config: auctionConfigPDA})
+                .createAssetAuction(createAssetAuctionLongArgs.durationMinutes, createAssetAuctionLongArgs.minBid, null, null, null)
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
                 .accounts({
                     collection: collectionPubkey,
                     asset: assetPubkey,
@@ -338,8 +347,8 @@ describe("Asset auction creation", () => {
 
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -419,8 +428,8 @@ describe("Asset auction creation", () => {
 
         // create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -481,8 +490,8 @@ describe("Asset auction creation", () => {
         
         // Create asset auction
         await program.methods
-            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-            .accountsPartial({config: auctionConfigPDA})
+            .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
             .accounts({
                 collection: collectionPubkey,
                 asset: assetPubkey,
@@ -493,8 +502,8 @@ describe("Asset auction creation", () => {
         let failed = false;
         try {
             await program.methods
-                .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-                .accountsPartial({config: auctionConfigPDA})
+                .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
                 .accounts({
                     collection: collectionPubkey,
                     asset: assetPubkey,
@@ -537,8 +546,8 @@ describe("Asset auction creation", () => {
         let failed = false;
         try {
             await program.methods
-                .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-                .accountsPartial({config: auctionConfigPDA})
+                .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
                 .accounts({
                     collection: collectionPubkey,
                     asset: assetPubkey,
@@ -598,8 +607,8 @@ describe("Asset auction creation", () => {
         let failed = false;
         try {
             await program.methods
-                .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-                .accountsPartial({config: auctionConfigPDA})
+                .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
                 .accounts({
                     collection: collectionPubkey,
                     asset: assetPubkey,
@@ -646,8 +655,8 @@ describe("Asset auction creation", () => {
         let failed = false;
         try {
             await program.methods
-                .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid)
-                .accountsPartial({config: auctionConfigPDA})
+                .createAssetAuction(createAssetAuctionArgs.durationMinutes, createAssetAuctionArgs.minBid, null, null, null)
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
                 .accounts({
                     collection: collectionPubkey,
                     asset: assetPubkey,
@@ -32,6 +32,8 @@ describe("Collection Whitelisting", () => {
         feeBPS: 100,
         minDurationMinutes: 60,
         maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
     };
 
     // config account pda
@@ -55,7 +57,9 @@ describe("Collection Whitelisting", () => {
                 initParams.seed,
                 initParams.feeBPS,
                 initParams.minDurationMinutes,
-                initParams.maxDurationMinutes
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
             )
             .rpc(); 
     });
@@ -71,7 +75,7 @@ describe("Collection Whitelisting", () => {
     it("create collection auction", async () => {
         // create collection auction
         await program.methods
-            .createCollectionAuction()
+            .createCollectionAuction(null)
             .accountsPartial({config: auctionConfigPDA})
             .accounts({collection: collectionPubkey})
             .rpc();
@@ -87,7 +91,40 @@ describe("Collection Whitelisting", () => {
 
         // verify values
         assert(collection_auction.collection.toBase58() === collectionPubkey.toBase58());
+        assert(collection_auction.extension === null);
+        assert(collection_auction.quoteMint === null);
+        assert(collection_auction.enabled);
+        assert(collection_auction.openAuctions === 0);
         assert(collection_auction.bump === bump);
     });
 
+    it("create collection auction with extension override", async () => {
+        // create collection
+        const overrideCollectionSigner = generateSigner(umi)
+        const overrideCollectionPubkey = new anchor.web3.PublicKey(overrideCollectionSigner.publicKey.toString());
+        await createCollection(umi, {...collectionArgs, collection: overrideCollectionSigner}).sendAndConfirm(umi);
+
+        // create collection auction
+        const extension = {windowMinutes: 10, extensionMinutes: 2, maxExtensions: 5};
+        await program.methods
+            .createCollectionAuction(extension)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: overrideCollectionPubkey})
+            .rpc();
+
+        // get collection auction account pda
+        const [auctionCollectionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("collection"), auctionConfigPDA.toBuffer(), overrideCollectionPubkey.toBuffer()],
+            program.programId,
+        );
+
+        // fetch collection auction pda
+        const collection_auction = await program.account.collectionAuction.fetch(auctionCollectionPDA);
+
+        // verify values
+        assert(collection_auction.extension?.windowMinutes === extension.windowMinutes);
+        assert(collection_auction.extension?.extensionMinutes === extension.extensionMinutes);
+        assert(collection_auction.extension?.maxExtensions === extension.maxExtensions);
+    });
+
 });
@@ -0,0 +1,260 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Dutch asset auction creation", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 7,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    it("create dutch asset auction", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create dutch asset auction args
+        const createDutchAssetAuctionArgs = {
+            durationMinutes: 60,
+            startPrice: new BN(2 * anchor.web3.LAMPORTS_PER_SOL),
+            floorPrice: new BN(anchor.web3.LAMPORTS_PER_SOL),
+            decay: {stepped: {stepMinutes: 10}},
+        }
+
+        // create dutch asset auction
+        await program.methods
+            .createDutchAssetAuction(
+                createDutchAssetAuctionArgs.durationMinutes,
+                createDutchAssetAuctionArgs.startPrice,
+                createDutchAssetAuctionArgs.floorPrice,
+                createDutchAssetAuctionArgs.decay,
+            )
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // get asset auction PDA
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+
+        // fetch asset auction account
+        const assetAuctionAccount = await program.account.assetAuction.fetch(assetAuctionPDA);
+        const dutch = assetAuctionAccount.kind.dutch
+        assert(dutch != undefined, "Auction is not dutch");
+        assert(dutch.startPriceLamports.eq(createDutchAssetAuctionArgs.startPrice));
+        assert(dutch.floorPriceLamports.eq(createDutchAssetAuctionArgs.floorPrice));
+        assert(dutch.decay.stepped?.stepMinutes === createDutchAssetAuctionArgs.decay.stepped.stepMinutes);
+        assert(assetAuctionAccount.endTimestamp.eq(
+            dutch.startTimestamp.add(new BN(createDutchAssetAuctionArgs.durationMinutes * 60))
+        ));
+
+        // verify custody
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.freezeDelegate?.frozen, "Asset is not frozen");
+        assert(asset.transferDelegate?.authority.address?.toString() === assetAuctionPDA.toBase58());
+    });
+
+    it("try create dutch asset auction with floor above start raises", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // should fail because floor price is higher than start price
+        let failed = false;
+        try {
+            await program.methods
+                .createDutchAssetAuction(
+                    60,
+                    new BN(anchor.web3.LAMPORTS_PER_SOL),
+                    new BN(2 * anchor.web3.LAMPORTS_PER_SOL),
+                    {linear: {}},
+                )
+                .accountsPartial({config: auctionConfigPDA})
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidDutchPrices")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try create dutch asset auction with step longer than duration raises", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // should fail because decay step is longer than the auction
+        let failed = false;
+        try {
+            await program.methods
+                .createDutchAssetAuction(
+                    60,
+                    new BN(2 * anchor.web3.LAMPORTS_PER_SOL),
+                    new BN(anchor.web3.LAMPORTS_PER_SOL),
+                    {stepped: {stepMinutes: 61}},
+                )
+                .accountsPartial({config: auctionConfigPDA})
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidPriceDecay")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
@@ -0,0 +1,340 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Emergency pause", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 14,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to create an english asset auction
+    async function createAssetAuction(assetPubkey: anchor.web3.PublicKey) {
+        await program.methods
+            .createAssetAuction(60, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    // helper function to bid on an asset auction
+    async function bidAssetAuction(assetPubkey: anchor.web3.PublicKey, buyer: anchor.web3.Keypair, lamports: BN) {
+        await program.methods
+            .bidAssetAuction(lamports, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+    }
+
+    // helper function to flip the circuit breaker
+    async function setPaused(paused: boolean) {
+        await program.methods
+            .setPaused(paused)
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+    }
+
+    it("pause blocks listing and bidding", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await createAssetAuction(assetPubkey);
+
+        await setPaused(true);
+        const auction_config = await program.account.config.fetch(auctionConfigPDA);
+        assert(auction_config.paused);
+
+        // should fail because program is paused
+        const asset2 = await createAsset();
+        const asset2Pubkey = new anchor.web3.PublicKey(asset2.publicKey.toString());
+        let create_failed = false;
+        try {
+            await createAssetAuction(asset2Pubkey);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "ProgramPaused")
+                create_failed = true
+            }
+        } finally {
+            assert(create_failed)
+        }
+
+        // should fail because program is paused
+        const buyer = await createSigner();
+        let bid_failed = false;
+        try {
+            await bidAssetAuction(assetPubkey, buyer, new BN(anchor.web3.LAMPORTS_PER_SOL));
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "ProgramPaused")
+                bid_failed = true
+            }
+        } finally {
+            assert(bid_failed)
+        }
+
+        await setPaused(false);
+    });
+
+    it("emergency refund", async () => {
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await createAssetAuction(assetPubkey);
+
+        const buyer = await createSigner();
+        await bidAssetAuction(assetPubkey, buyer, new BN(2 * anchor.web3.LAMPORTS_PER_SOL));
+
+        // refund bid and hand asset back while paused
+        await setPaused(true);
+        await program.methods
+            .emergencyRefundAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                owner: signer.publicKey,
+                funder: buyer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+        await setPaused(false);
+
+        // check asset is back to owner and unfrozen
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == signer.publicKey.toString());
+        assert(asset.freezeDelegate?.frozen == false);
+        assert(asset.freezeDelegate?.authority.type == "Owner");
+        assert(asset.transferDelegate?.authority.type == "Owner");
+
+        // check funder is refunded and auction is closed
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const buyerAccountInfo = await provider.connection.getAccountInfo(buyer.publicKey);
+        const assetAuctionAccountInfo = await provider.connection.getAccountInfo(assetAuctionPDA);
+        assert(buyerAccountInfo?.lamports == 10 * anchor.web3.LAMPORTS_PER_SOL);
+        assert(assetAuctionAccountInfo == null);
+    });
+
+    it("try emergency refund while not paused raises", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await createAssetAuction(assetPubkey);
+
+        let failed = false;
+        try {
+            await program.methods
+                .emergencyRefundAssetAuction()
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    owner: signer.publicKey,
+                    funder: signer.publicKey,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "ProgramNotPaused")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("pauser can pause", async () => {
+        const pauser = await createSigner();
+        const other = await createSigner();
+
+        await program.methods
+            .setPauser(pauser.publicKey)
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+
+        await program.methods
+            .setPaused(true)
+            .accountsPartial({config: auctionConfigPDA, authority: pauser.publicKey})
+            .signers([pauser])
+            .rpc();
+        let auction_config = await program.account.config.fetch(auctionConfigPDA);
+        assert(auction_config.paused);
+
+        // should fail because signer is neither admin nor pauser
+        let failed = false;
+        try {
+            await program.methods
+                .setPaused(false)
+                .accountsPartial({config: auctionConfigPDA, authority: other.publicKey})
+                .signers([other])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidPauser")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        // admin can always resume
+        await setPaused(false);
+        auction_config = await program.account.config.fetch(auctionConfigPDA);
+        assert(!auction_config.paused);
+    });
+});
@@ -0,0 +1,360 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Auction events", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 18,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    // signatures of the setup transactions, checked for events
+    let initializeSignature: string;
+    let collectionSignature: string;
+
+    before("intialize auction", async () => {
+        initializeSignature = await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc({commitment: "confirmed"});
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        collectionSignature = await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc({commitment: "confirmed"});
+    });
+
+    // helper function to decode events emitted with emit_cpi, they are inner instructions to the program
+    async function fetchEvents(signature: string): Promise<anchor.Event[]> {
+        const tx = await provider.connection.getTransaction(signature, {
+            commitment: "confirmed",
+            maxSupportedTransactionVersion: 0,
+        });
+        const accountKeys = tx!.transaction.message.getAccountKeys();
+        const eventIxTag = Buffer.from([0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]);
+
+        const events: anchor.Event[] = [];
+        for (const inner of tx!.meta!.innerInstructions!) {
+            for (const ix of inner.instructions) {
+                const data = Buffer.from(anchor.utils.bytes.bs58.decode(ix.data));
+                if (!accountKeys.get(ix.programIdIndex)!.equals(program.programId) || !data.subarray(0, 8).equals(eventIxTag)) {
+                    continue;
+                }
+                const event = program.coder.events.decode(anchor.utils.bytes.base64.encode(data.subarray(8)));
+                if (event) {
+                    events.push(event);
+                }
+            }
+        }
+        return events;
+    }
+
+    it("config initialized", async () => {
+        const [event] = await fetchEvents(initializeSignature);
+        assert(event.name == "configInitialized");
+        assert(event.data.config.equals(auctionConfigPDA));
+        assert(event.data.admin.equals(payerWallet.publicKey));
+        assert(event.data.seed == initParams.seed);
+        assert(event.data.feeBps == initParams.feeBPS);
+        assert(event.data.maxDurationMinutes == initParams.maxDurationMinutes);
+    });
+
+    it("collection whitelisted", async () => {
+        const [event] = await fetchEvents(collectionSignature);
+        assert(event.name == "collectionWhitelisted");
+        assert(event.data.config.equals(auctionConfigPDA));
+        assert(event.data.collection.equals(collectionPubkey));
+        assert(event.data.collectionAuction.equals(auctionCollectionPDA));
+        assert(event.data.quoteMint == null);
+    });
+
+    it("auction lifecycle", async () => {
+        // create asset
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const [assetAuctionPDA, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+
+        // create asset auction
+        const minBid = new BN(100);
+        let signature = await program.methods
+            .createAssetAuction(0, minBid, null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc({commitment: "confirmed"});
+
+        const [created] = await fetchEvents(signature);
+        assert(created.name == "auctionCreated");
+        assert(created.data.collection.equals(collectionPubkey));
+        assert(created.data.asset.equals(assetPubkey));
+        assert(created.data.assetAuction.equals(assetAuctionPDA));
+        assert(created.data.owner.equals(payerWallet.publicKey));
+        assert(created.data.kind.english !== undefined);
+        assert(created.data.minBidLamports.eq(minBid));
+
+        // opening bid
+        const firstBuyer = await createSigner();
+        const firstBid = new BN(anchor.web3.LAMPORTS_PER_SOL);
+        signature = await program.methods
+            .bidAssetAuction(firstBid, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: firstBuyer.publicKey,
+                payer: firstBuyer.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([firstBuyer])
+            .rpc({commitment: "confirmed"});
+
+        const [opening] = await fetchEvents(signature);
+        assert(opening.name == "bidPlaced");
+        assert(opening.data.bidder.equals(firstBuyer.publicKey));
+        assert(opening.data.bidLamports.eq(firstBid));
+        assert(opening.data.previousBidder == null);
+        assert(opening.data.refundLamports.eqn(0));
+
+        // outbid, previous bid is credited to its refund account
+        const secondBuyer = await createSigner();
+        const secondBid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL);
+        signature = await program.methods
+            .bidAssetAuction(secondBid, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: secondBuyer.publicKey,
+                payer: secondBuyer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([secondBuyer])
+            .rpc({commitment: "confirmed"});
+
+        const [outbid] = await fetchEvents(signature);
+        assert(outbid.name == "bidPlaced");
+        assert(outbid.data.bidder.equals(secondBuyer.publicKey));
+        assert(outbid.data.funder.equals(secondBuyer.publicKey));
+        assert(outbid.data.previousBidder.equals(firstBuyer.publicKey));
+        assert(outbid.data.previousBidLamports.eq(firstBid));
+        assert(outbid.data.refundLamports.eq(firstBid));
+
+        // first buyer withdraws its refund
+        const [bidRefundPDA, _6] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("refund"), assetAuctionPDA.toBuffer(), firstBuyer.publicKey.toBuffer(), firstBuyer.publicKey.toBuffer()],
+            program.programId,
+        );
+        signature = await program.methods
+            .withdrawRefund()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                authority: firstBuyer.publicKey,
+                funder: firstBuyer.publicKey,
+                rentPayer: secondBuyer.publicKey,
+                bidRefund: bidRefundPDA,
+            })
+            .signers([firstBuyer])
+            .rpc({commitment: "confirmed"});
+
+        const [withdrawn] = await fetchEvents(signature);
+        assert(withdrawn.name == "refundWithdrawn");
+        assert(withdrawn.data.auction.equals(assetAuctionPDA));
+        assert(withdrawn.data.bidder.equals(firstBuyer.publicKey));
+        assert(withdrawn.data.funder.equals(firstBuyer.publicKey));
+        assert(withdrawn.data.quoteMint == null);
+        assert(withdrawn.data.lamports.eq(firstBid));
+
+        // complete auction
+        signature = await program.methods
+            .completeAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                buyer: secondBuyer.publicKey,
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc({commitment: "confirmed"});
+
+        const [completed] = await fetchEvents(signature);
+        assert(completed.name == "auctionCompleted");
+        assert(completed.data.collection.equals(collectionPubkey));
+        assert(completed.data.asset.equals(assetPubkey));
+        assert(completed.data.seller.equals(payerWallet.publicKey));
+        assert(completed.data.buyer.equals(secondBuyer.publicKey));
+        assert(completed.data.priceLamports.eq(secondBid));
+        assert(completed.data.feeLamports.eq(secondBid.divn(100)));
+        assert(completed.data.royaltyLamports.eqn(0));
+        assert(completed.data.sellerLamports.eq(secondBid.sub(completed.data.feeLamports)));
+    });
+
+    it("auction cancelled", async () => {
+        // create asset
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction
+        await program.methods
+            .createAssetAuction(0, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // cancel asset auction
+        const signature = await program.methods
+            .cancelAssetAuction()
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc({commitment: "confirmed"});
+
+        const [cancelled] = await fetchEvents(signature);
+        assert(cancelled.name == "auctionCancelled");
+        assert(cancelled.data.collection.equals(collectionPubkey));
+        assert(cancelled.data.asset.equals(assetPubkey));
+        assert(cancelled.data.owner.equals(payerWallet.publicKey));
+    });
+});
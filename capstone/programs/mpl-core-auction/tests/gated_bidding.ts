@@ -0,0 +1,320 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+    publicKey,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+    createMint,
+    getOrCreateAssociatedTokenAccount,
+    mintTo,
+} from "@solana/spl-token";
+
+
+describe("Gated bidding", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 26,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+
+    // gate collection params, holders of this collection get first access
+    const gateCollectionSigner = generateSigner(umi)
+    const gateCollectionPubkey = new anchor.web3.PublicKey(gateCollectionSigner.publicKey.toString());
+
+    // gate mint, created in the before hook
+    let gateMint: anchor.web3.PublicKey;
+
+    before("create gate collection and mint", async () => {
+        await createCollection(umi, {
+            collection: gateCollectionSigner,
+            name: 'Gate Collection',
+            uri: "",
+        }).sendAndConfirm(umi);
+
+        gateMint = await createMint(provider.connection, payerWallet.payer, payerWallet.publicKey, null, 0);
+    });
+
+    // helper function to create an asset of the gate collection owned by a holder
+    async function createGateAsset(owner: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> {
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, gateCollectionSigner.publicKey);
+        await create(umi, {
+            collection: collection,
+            asset: assetSigner,
+            name: 'Gate asset',
+            uri: "",
+            owner: publicKey(owner.toString()),
+        }).sendAndConfirm(umi);
+        return new anchor.web3.PublicKey(assetSigner.publicKey.toString());
+    }
+
+    // helper function to give a holder a balance of the gate mint
+    async function mintGateTokens(owner: anchor.web3.PublicKey, amount: number): Promise<anchor.web3.PublicKey> {
+        const tokenAccount = await getOrCreateAssociatedTokenAccount(
+            provider.connection, payerWallet.payer, gateMint, owner,
+        );
+        if (amount > 0) {
+            await mintTo(provider.connection, payerWallet.payer, gateMint, tokenAccount.address, payerWallet.payer, amount);
+        }
+        return tokenAccount.address;
+    }
+
+    // helper function to set the collection bid gate
+    async function setGate(gate: any, admin: anchor.web3.Keypair = payerWallet.payer) {
+        await program.methods
+            .setCollectionGate(gate)
+            .accountsPartial({
+                admin: admin.publicKey,
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+            })
+            .signers([admin])
+            .rpc();
+    }
+
+    // helper function to create an english asset auction
+    async function createAssetAuction(): Promise<anchor.web3.PublicKey> {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await program.methods
+            .createAssetAuction(60, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+        return assetPubkey;
+    }
+
+    // helper function to place the opening bid with optional gate accounts
+    async function bid(assetPubkey: anchor.web3.PublicKey, buyer: anchor.web3.Keypair, gateAsset: anchor.web3.PublicKey | null, gateTokenAccount: anchor.web3.PublicKey | null) {
+        await program.methods
+            .bidAssetAuction(new BN(LAMPORTS_PER_SOL), [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+                gateAsset,
+                gateTokenAccount,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+    }
+
+    // helper function to assert that a call fails with the given error code
+    async function assertFails(call: Promise<any>, code: string) {
+        let failed = false;
+        try {
+            await call;
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === code)
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    }
+
+    it("only the admin can set a valid gate", async () => {
+        const stranger = await createSigner();
+        await assertFails(setGate({collection: {collection: gateCollectionPubkey}}, stranger), "InvalidAdmin");
+        await assertFails(setGate({token: {mint: gateMint, minAmount: new BN(0)}}), "InvalidGate");
+    });
+
+    it("collection holders can bid on a collection gated auction", async () => {
+        await setGate({collection: {collection: gateCollectionPubkey}});
+        const collectionAuction = await program.account.collectionAuction.fetch(auctionCollectionPDA);
+        assert(collectionAuction.gate.collection.collection.equals(gateCollectionPubkey));
+
+        const holder = await createSigner();
+        const gateAsset = await createGateAsset(holder.publicKey);
+        const assetPubkey = await createAssetAuction();
+        await bid(assetPubkey, holder, gateAsset, null);
+    });
+
+    it("bidders without a gate asset can not bid", async () => {
+        await setGate({collection: {collection: gateCollectionPubkey}});
+        const holder = await createSigner();
+        const gateAsset = await createGateAsset(holder.publicKey);
+        const assetPubkey = await createAssetAuction();
+
+        // no gate account
+        const outsider = await createSigner();
+        await assertFails(bid(assetPubkey, outsider, null, null), "MissingGateAccount");
+
+        // asset of someone else
+        await assertFails(bid(assetPubkey, outsider, gateAsset, null), "GateNotMet");
+
+        // asset outside the gate collection
+        const asset = await createAsset();
+        const assetOutside = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await assertFails(bid(assetPubkey, outsider, assetOutside, null), "GateNotMet");
+    });
+
+    it("token holders can bid on a token gated auction", async () => {
+        await setGate({token: {mint: gateMint, minAmount: new BN(10)}});
+        const assetPubkey = await createAssetAuction();
+
+        // balance under the minimum
+        const poorHolder = await createSigner();
+        const poorTokenAccount = await mintGateTokens(poorHolder.publicKey, 9);
+        await assertFails(bid(assetPubkey, poorHolder, null, poorTokenAccount), "GateNotMet");
+
+        const holder = await createSigner();
+        const tokenAccount = await mintGateTokens(holder.publicKey, 10);
+        await bid(assetPubkey, holder, null, tokenAccount);
+    });
+
+    it("clearing the gate opens bidding to the public", async () => {
+        await setGate(null);
+        const collectionAuction = await program.account.collectionAuction.fetch(auctionCollectionPDA);
+        assert(collectionAuction.gate == null);
+
+        const buyer = await createSigner();
+        const assetPubkey = await createAssetAuction();
+        await bid(assetPubkey, buyer, null, null);
+    });
+});
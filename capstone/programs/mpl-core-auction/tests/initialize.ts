@@ -20,6 +20,8 @@ describe("Auction initialization", () => {
         feeBPS: 100,
         minDurationMinutes: 60,
         maxDurationMinutes: 14400,
+        extension: {windowMinutes: 5, extensionMinutes: 5, maxExtensions: 3},
+        minBidIncrement: {lamports: {amount: new BN(1000)}},
     };
 
     // config account pda
@@ -47,7 +49,9 @@ describe("Auction initialization", () => {
                 initParams.seed,
                 initParams.feeBPS,
                 initParams.minDurationMinutes,
-                initParams.maxDurationMinutes
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
             )
             .rpc(); 
 
@@ -60,6 +64,10 @@ describe("Auction initialization", () => {
         assert(auction_config.feeBps === initParams.feeBPS);
         assert(auction_config.minDurationMinutes === initParams.minDurationMinutes);
         assert(auction_config.maxDurationMinutes === initParams.maxDurationMinutes);
+        assert(auction_config.extension.windowMinutes === initParams.extension.windowMinutes);
+        assert(auction_config.extension.extensionMinutes === initParams.extension.extensionMinutes);
+        assert(auction_config.extension.maxExtensions === initParams.extension.maxExtensions);
+        assert(auction_config.minBidIncrement.lamports?.amount.eq(initParams.minBidIncrement.lamports.amount));
         assert(auction_config.vaultBump === vaultBump);
         assert(auction_config.tresuaryBump === tresuaryBump);
         assert(auction_config.bump === bump);
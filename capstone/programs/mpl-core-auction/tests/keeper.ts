@@ -0,0 +1,344 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+import { execFileSync } from "child_process";
+import * as fs from "fs";
+import * as os from "os";
+import * as path from "path";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Keeper", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 16,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+
+    // keeper reward paid out of the fee
+    const keeperReward = {lamports: {amount: new BN(1_000_000)}};
+
+    before("set keeper reward", async () => {
+        await program.methods
+            .updateConfig(
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+                keeperReward,
+            )
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+    });
+
+    // helper function to get the cluster unix timestamp
+    async function getUnixTimestamp(): Promise<number> {
+        const slot = await provider.connection.getSlot();
+        return (await provider.connection.getBlockTime(slot))!;
+    }
+
+    // helper function to wait for the cluster clock
+    async function sleep(seconds: number) {
+        await new Promise((resolve) => setTimeout(resolve, seconds * 1000));
+    }
+
+    // helper function to create an asset auction ending in a few seconds
+    async function createEndingAssetAuction(assetPubkey: anchor.web3.PublicKey, listingReferrer: anchor.web3.PublicKey | null = null): Promise<anchor.web3.PublicKey> {
+        const now = await getUnixTimestamp();
+        await program.methods
+            .createAssetAuction(0, new BN(100), null, null, {startTs: new BN(now - 60), endTs: new BN(now + 2)})
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA, listingReferrer})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        return assetAuctionPDA
+    }
+
+    // helper function to run the keeper binary once against the test validator
+    function runKeeper(keeper: anchor.web3.Keypair) {
+        const keypairPath = path.join(os.tmpdir(), `keeper-${keeper.publicKey.toBase58()}.json`);
+        fs.writeFileSync(keypairPath, JSON.stringify(Array.from(keeper.secretKey)));
+
+        execFileSync("cargo", [
+            "run", "--quiet", "-p", "mpl-core-auction-keeper", "--",
+            "--url", provider.connection.rpcEndpoint,
+            "--keypair", keypairPath,
+            "--config", auctionConfigPDA.toBase58(),
+            "--once",
+        ], {stdio: "inherit"});
+    }
+
+    it("keeper completes ended auction with bid", async () => {
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createEndingAssetAuction(assetPubkey);
+
+        const buyer = await createSigner();
+        await program.methods
+            .bidAssetAuction(new BN(LAMPORTS_PER_SOL), [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+        await sleep(4);
+
+        const keeper = await createSigner();
+        const keeperLamports = await provider.connection.getBalance(keeper.publicKey);
+        runKeeper(keeper);
+
+        // asset goes to buyer and the auction is closed
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+        assert(await provider.connection.getAccountInfo(assetAuctionPDA) === null);
+
+        // keeper reward is carved out of the fee
+        const feeLamports = LAMPORTS_PER_SOL * initParams.feeBPS / 10_000;
+        const tresuaryLamports = await provider.connection.getBalance(tresuaryPDA);
+        assert(tresuaryLamports === feeLamports - keeperReward.lamports.amount.toNumber());
+        assert(await provider.connection.getBalance(keeper.publicKey) > keeperLamports);
+    });
+
+    it("keeper expires ended scheduled auction without bids", async () => {
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const assetAuctionPDA = await createEndingAssetAuction(assetPubkey);
+        await sleep(4);
+
+        const keeper = await createSigner();
+        const tresuaryLamports = await provider.connection.getBalance(tresuaryPDA);
+        runKeeper(keeper);
+
+        // asset goes back to owner and the auction is closed
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == signer.publicKey.toString());
+        assert(asset.freezeDelegate?.frozen == false);
+        assert(await provider.connection.getAccountInfo(assetAuctionPDA) === null);
+
+        // no sale means no fee and no keeper reward
+        const expiredTresuaryLamports = await provider.connection.getBalance(tresuaryPDA);
+        assert(expiredTresuaryLamports === tresuaryLamports);
+    });
+
+    it("keeper pays the referrers of a referred auction", async () => {
+        // referrer shares of the protocol fee, in bps of the fee
+        const referrerFee = {bidBps: 2000, listingBps: 1000};
+        await program.methods
+            .setReferrerFee(referrerFee)
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        const listingReferrer = await createSigner();
+        const assetAuctionPDA = await createEndingAssetAuction(assetPubkey, listingReferrer.publicKey);
+
+        const buyer = await createSigner();
+        const bidReferrer = await createSigner();
+        await program.methods
+            .bidAssetAuction(new BN(LAMPORTS_PER_SOL), [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+                referrer: bidReferrer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+        await sleep(4);
+
+        // keeper passes the referrers recorded on the auction
+        const keeper = await createSigner();
+        runKeeper(keeper);
+
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+        assert(await provider.connection.getAccountInfo(assetAuctionPDA) === null);
+
+        // referrer shares are carved out of the fee
+        const feeLamports = LAMPORTS_PER_SOL * initParams.feeBPS / 10_000;
+        assert(await provider.connection.getBalance(bidReferrer.publicKey) === 10 * LAMPORTS_PER_SOL + feeLamports * referrerFee.bidBps / 10_000);
+        assert(await provider.connection.getBalance(listingReferrer.publicKey) === 10 * LAMPORTS_PER_SOL + feeLamports * referrerFee.listingBps / 10_000);
+    });
+
+    it("try update config with invalid keeper reward raises", async () => {
+        let failed = false;
+        try {
+            await program.methods
+                .updateConfig(
+                    initParams.feeBPS,
+                    initParams.minDurationMinutes,
+                    initParams.maxDurationMinutes,
+                    initParams.extension,
+                    initParams.minBidIncrement,
+                    {bps: {bps: 10_001}},
+                )
+                .accountsPartial({config: auctionConfigPDA})
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidKeeperReward")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
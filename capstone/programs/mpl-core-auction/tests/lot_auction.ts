@@ -0,0 +1,438 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    addPlugin,
+    AssetV1,
+    ruleSet,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+    publicKey,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Lot auction", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 24,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // collection stats pda
+    const [collectionStatsPDA, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("stats"), auctionCollectionPDA.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to derive a lot auction pda
+    function lotAuctionPDA(seed: number): anchor.web3.PublicKey {
+        const [lotAuction, _6] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("lot"), auctionCollectionPDA.toBuffer(), payerWallet.publicKey.toBuffer(), new BN(seed).toArrayLike(Buffer, "le", 4)],
+            program.programId,
+        );
+        return lotAuction;
+    }
+
+    // helper function to create a lot auction of new assets, assets are passed as remaining accounts
+    async function createLotAuction(seed: number, durationMinutes: number, size: number): Promise<anchor.web3.PublicKey[]> {
+        const assets: anchor.web3.PublicKey[] = [];
+        for (let i = 0; i < size; i++) {
+            const asset = await createAsset();
+            assets.push(new anchor.web3.PublicKey(asset.publicKey.toString()));
+        }
+
+        await program.methods
+            .createLotAuction(seed, durationMinutes, new BN(100))
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .remainingAccounts(assets.map((pubkey) => ({pubkey, isWritable: true, isSigner: false})))
+            .rpc();
+        return assets;
+    }
+
+    it("create, bid and complete lot auction", async () => {
+        const assets = await createLotAuction(0, 0, 3);
+
+        // lot stores the assets, every asset is frozen
+        const lotAuction = await program.account.lotAuction.fetch(lotAuctionPDA(0));
+        assert(lotAuction.assets.length == 3);
+        for (const [index, assetPubkey] of assets.entries()) {
+            assert(lotAuction.assets[index].equals(assetPubkey));
+            const asset = await fetchAsset(umi, assetPubkey.toString());
+            assert(asset.freezeDelegate?.frozen == true);
+        }
+
+        // one bid covers the whole lot
+        const buyer = await createSigner();
+        await program.methods
+            .bidLotAuction(new BN(LAMPORTS_PER_SOL))
+            .accountsPartial({
+                config: auctionConfigPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                lotAuction: lotAuctionPDA(0),
+                previousBidRefund: null,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // complete, every asset goes to the buyer
+        await program.methods
+            .completeLotAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                owner: signer.publicKey,
+                buyer: buyer.publicKey,
+                lotAuction: lotAuctionPDA(0),
+            })
+            .accounts({collection: collectionPubkey})
+            .remainingAccounts(assets.map((pubkey) => ({pubkey, isWritable: true, isSigner: false})))
+            .rpc();
+
+        for (const assetPubkey of assets) {
+            const asset = await fetchAsset(umi, assetPubkey.toString());
+            assert(asset.owner.toString() == buyer.publicKey.toBase58());
+            assert(asset.freezeDelegate?.frozen == false);
+        }
+        const lotAuctionAccountInfo = await provider.connection.getAccountInfo(lotAuctionPDA(0));
+        assert(lotAuctionAccountInfo == null);
+
+        // stats record a sale per asset, the last one takes the rounding remainder
+        const stats = await program.account.collectionStats.fetch(collectionStatsPDA);
+        assert(stats.sales.eqn(3));
+        assert(stats.volumeLamports.eq(new BN(LAMPORTS_PER_SOL)));
+        for (const [index, assetPubkey] of assets.entries()) {
+            assert(stats.recentSales[index].asset.equals(assetPubkey));
+        }
+        assert(stats.recentSales[0].priceLamports.eq(new BN(333_333_333)));
+        assert(stats.recentSales[2].priceLamports.eq(new BN(333_333_334)));
+    });
+
+    it("cancel lot auction", async () => {
+        const assets = await createLotAuction(1, 60, 2);
+
+        await program.methods
+            .cancelLotAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                lotAuction: lotAuctionPDA(1),
+            })
+            .accounts({collection: collectionPubkey})
+            .remainingAccounts(assets.map((pubkey) => ({pubkey, isWritable: true, isSigner: false})))
+            .rpc();
+
+        // every asset is thawed and handed back
+        for (const assetPubkey of assets) {
+            const asset = await fetchAsset(umi, assetPubkey.toString());
+            assert(asset.owner.toString() == signer.publicKey.toString());
+            assert(asset.freezeDelegate?.authority.type == "Owner");
+            assert(asset.transferDelegate?.authority.type == "Owner");
+        }
+        const lotAuctionAccountInfo = await provider.connection.getAccountInfo(lotAuctionPDA(1));
+        assert(lotAuctionAccountInfo == null);
+    });
+
+    it("lot assets must be passed in order", async () => {
+        const assets = await createLotAuction(2, 60, 2);
+
+        let failed = false;
+        try {
+            await program.methods
+                .cancelLotAuction()
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    lotAuction: lotAuctionPDA(2),
+                })
+                .accounts({collection: collectionPubkey})
+                .remainingAccounts(assets.reverse().map((pubkey) => ({pubkey, isWritable: true, isSigner: false})))
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidLotAssets")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try lot of assets with different royalties raises", async () => {
+        // second asset has its own royalties plugin, the first one has none
+        const asset = await createAsset();
+        const royaltiesSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        await create(umi, {
+            collection: collection,
+            asset: royaltiesSigner,
+            name: 'My asset',
+            uri: "",
+            plugins: [{
+                type: 'Royalties',
+                basisPoints: 500,
+                creators: [{address: publicKey(payerWallet.publicKey.toBase58()), percentage: 100}],
+                ruleSet: ruleSet('None'),
+            }],
+        }).sendAndConfirm(umi);
+        const assets = [asset.publicKey, royaltiesSigner.publicKey].map((pubkey) => new anchor.web3.PublicKey(pubkey.toString()));
+
+        let failed = false;
+        try {
+            await program.methods
+                .createLotAuction(3, 60, new BN(100))
+                .accountsPartial({config: auctionConfigPDA})
+                .accounts({collection: collectionPubkey})
+                .remainingAccounts(assets.map((pubkey) => ({pubkey, isWritable: true, isSigner: false})))
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "LotRoyaltiesMismatch")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try complete after the royalties of a lot asset changed raises", async () => {
+        const assets = await createLotAuction(5, 0, 2);
+
+        const buyer = await createSigner();
+        await program.methods
+            .bidLotAuction(new BN(LAMPORTS_PER_SOL))
+            .accountsPartial({
+                config: auctionConfigPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                lotAuction: lotAuctionPDA(5),
+                previousBidRefund: null,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // update authority adds royalties to the second asset after the lot was created
+        await addPlugin(umi, {
+            asset: publicKey(assets[1].toBase58()),
+            collection: collectionSigner.publicKey,
+            plugin: {
+                type: 'Royalties',
+                basisPoints: 500,
+                creators: [{address: publicKey(payerWallet.publicKey.toBase58()), percentage: 100}],
+                ruleSet: ruleSet('None'),
+            },
+        }).sendAndConfirm(umi);
+
+        let failed = false;
+        try {
+            await program.methods
+                .completeLotAuction()
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    owner: signer.publicKey,
+                    buyer: buyer.publicKey,
+                    lotAuction: lotAuctionPDA(5),
+                })
+                .accounts({collection: collectionPubkey})
+                .remainingAccounts(assets.map((pubkey) => ({pubkey, isWritable: true, isSigner: false})))
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "LotRoyaltiesMismatch")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("emergency refund lot auction while paused", async () => {
+        const assets = await createLotAuction(4, 60, 2);
+
+        const buyer = await createSigner();
+        await program.methods
+            .bidLotAuction(new BN(LAMPORTS_PER_SOL))
+            .accountsPartial({
+                config: auctionConfigPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                lotAuction: lotAuctionPDA(4),
+                previousBidRefund: null,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // helper function to emergency refund the lot
+        async function emergencyRefund() {
+            await program.methods
+                .emergencyRefundLotAuction()
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    owner: signer.publicKey,
+                    funder: buyer.publicKey,
+                    lotAuction: lotAuctionPDA(4),
+                })
+                .accounts({collection: collectionPubkey})
+                .remainingAccounts(assets.map((pubkey) => ({pubkey, isWritable: true, isSigner: false})))
+                .rpc();
+        }
+
+        // should fail because program is not paused
+        let failed = false;
+        try {
+            await emergencyRefund();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "ProgramNotPaused")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        await program.methods.setPaused(true).accountsPartial({config: auctionConfigPDA}).rpc();
+        const buyerLamports = (await provider.connection.getAccountInfo(buyer.publicKey))!.lamports;
+        await emergencyRefund();
+        await program.methods.setPaused(false).accountsPartial({config: auctionConfigPDA}).rpc();
+
+        // high bid goes back to its funder and every asset is thawed for the owner
+        const refundedBuyerLamports = (await provider.connection.getAccountInfo(buyer.publicKey))!.lamports;
+        assert(refundedBuyerLamports == buyerLamports + LAMPORTS_PER_SOL);
+        for (const assetPubkey of assets) {
+            const asset = await fetchAsset(umi, assetPubkey.toString());
+            assert(asset.owner.toString() == signer.publicKey.toString());
+            assert(asset.freezeDelegate?.frozen == false);
+            assert(asset.freezeDelegate?.authority.type == "Owner");
+        }
+        const lotAuctionAccountInfo = await provider.connection.getAccountInfo(lotAuctionPDA(4));
+        assert(lotAuctionAccountInfo == null);
+    });
+});
@@ -0,0 +1,171 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+import fs from "fs";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+
+
+describe("Migrate config", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+    const payerWallet = provider.wallet as NodeWallet;
+
+    // legacy layout configs loaded by the validator from tests/fixtures, both share one admin
+    const admin = anchor.web3.Keypair.fromSecretKey(
+        Uint8Array.from(JSON.parse(fs.readFileSync("tests/fixtures/legacy_config_admin.json", "utf-8"))),
+    );
+
+    // helper function to derive a config pda and its vault and tresuary bumps
+    function configPDAs(seed: number): {config: anchor.web3.PublicKey, bump: number, vaultBump: number, tresuaryBump: number} {
+        const [config, bump] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("config"), new BN(seed).toArrayLike(Buffer, "le", 4)],
+            program.programId,
+        );
+        const [_, vaultBump] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("vault"), config.toBuffer()],
+            program.programId,
+        );
+        const [_2, tresuaryBump] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("tresuary"), config.toBuffer()],
+            program.programId,
+        );
+        return {config, bump, vaultBump, tresuaryBump};
+    }
+
+    // baseline layout config, seed 30
+    const {config: auctionConfigPDA, bump: configBump, vaultBump, tresuaryBump} = configPDAs(30);
+
+    // fee_bps u16 layout config without referrer fees, seed 31
+    const v1 = configPDAs(31);
+
+    before("fund legacy admin", async () => {
+        const airdropSignature = await provider.connection.requestAirdrop(
+            admin.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+    });
+
+    it("try migrate with other admin raises", async () => {
+        let failed = false;
+        try {
+            await program.methods
+                .migrateConfig()
+                .accountsPartial({
+                    admin: payerWallet.publicKey,
+                    config: auctionConfigPDA,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidAdmin")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("migrate baseline config", async () => {
+        // baseline layout can not be read as a config
+        const legacyAccountInfo = await provider.connection.getAccountInfo(auctionConfigPDA);
+        assert(legacyAccountInfo?.data.length == 56);
+
+        await program.methods
+            .migrateConfig()
+            .accountsPartial({
+                admin: admin.publicKey,
+                config: auctionConfigPDA,
+            })
+            .signers([admin])
+            .rpc();
+
+        // baseline fields are kept, new settings start disabled
+        const config = await program.account.config.fetch(auctionConfigPDA);
+        assert(config.seed == 30);
+        assert(config.admin.equals(admin.publicKey));
+        assert(config.pendingAdmin == null);
+        assert(config.pauser.equals(admin.publicKey));
+        assert(!config.paused);
+        assert(config.feeBps == 100);
+        assert(config.minDurationMinutes == 0);
+        assert(config.maxDurationMinutes == 14400);
+        assert(config.extension.maxExtensions == 0);
+        assert(config.minBidIncrement.lamports?.amount.eqn(0));
+        assert(config.keeperReward == null);
+        assert(config.referrerFee == null);
+        assert(config.vaultBump == vaultBump);
+        assert(config.tresuaryBump == tresuaryBump);
+        assert(config.bump == configBump);
+
+        // migrated account is rent exempt in the current layout
+        const accountInfo = await provider.connection.getAccountInfo(auctionConfigPDA);
+        const rent = await provider.connection.getMinimumBalanceForRentExemption(accountInfo!.data.length);
+        assert(accountInfo!.lamports >= rent);
+    });
+
+    it("try migrate twice raises", async () => {
+        let failed = false;
+        try {
+            await program.methods
+                .migrateConfig()
+                .accountsPartial({
+                    admin: admin.publicKey,
+                    config: auctionConfigPDA,
+                })
+                .signers([admin])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "ConfigAlreadyMigrated")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("migrate config without referrer fees", async () => {
+        // fee_bps u16 layout is recognised by its size
+        const legacyAccountInfo = await provider.connection.getAccountInfo(v1.config);
+        assert(legacyAccountInfo?.data.length == 152);
+
+        await program.methods
+            .migrateConfig()
+            .accountsPartial({
+                admin: admin.publicKey,
+                config: v1.config,
+            })
+            .signers([admin])
+            .rpc();
+
+        // every field is kept, referrer fees start disabled
+        const config = await program.account.config.fetch(v1.config);
+        assert(config.seed == 31);
+        assert(config.admin.equals(admin.publicKey));
+        assert(config.pendingAdmin == null);
+        assert(config.pauser.equals(admin.publicKey));
+        assert(!config.paused);
+        assert(config.feeBps == 250);
+        assert(config.maxDurationMinutes == 14400);
+        assert(config.extension.windowMinutes == 5);
+        assert(config.extension.extensionMinutes == 5);
+        assert(config.extension.maxExtensions == 3);
+        assert(config.minBidIncrement.bps?.bps == 500);
+        assert(config.keeperReward?.lamports?.amount.eqn(1000));
+        assert(config.referrerFee == null);
+        assert(config.vaultBump == v1.vaultBump);
+        assert(config.tresuaryBump == v1.tresuaryBump);
+        assert(config.bump == v1.bump);
+    });
+});
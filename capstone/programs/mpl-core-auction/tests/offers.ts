@@ -0,0 +1,293 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Offers", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 21,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to derive an offer pda
+    function offerPDA(assetPubkey: anchor.web3.PublicKey, bidder: anchor.web3.PublicKey): anchor.web3.PublicKey {
+        const [offer, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("offer"), auctionConfigPDA.toBuffer(), assetPubkey.toBuffer(), bidder.toBuffer()],
+            program.programId,
+        );
+        return offer;
+    }
+
+    // helper function to make an offer expiring in an hour
+    async function makeOffer(assetPubkey: anchor.web3.PublicKey, bidder: anchor.web3.Keypair, lamports: BN) {
+        const expiryTimestamp = new BN(Math.floor(Date.now() / 1000) + 3600);
+        await program.methods
+            .makeOffer(lamports, expiryTimestamp)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({
+                bidder: bidder.publicKey,
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([bidder])
+            .rpc();
+    }
+
+    it("make and cancel offer", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // make offer, lamports are escrowed in the vault
+        const bidder = await createSigner();
+        const offerLamports = new BN(LAMPORTS_PER_SOL);
+        const vaultInitialLamports = (await provider.connection.getAccountInfo(vaultPDA))?.lamports ?? 0;
+        await makeOffer(assetPubkey, bidder, offerLamports);
+
+        const offer = await program.account.offer.fetch(offerPDA(assetPubkey, bidder.publicKey));
+        assert(offer.config.equals(auctionConfigPDA));
+        assert(offer.collection.equals(collectionPubkey));
+        assert(offer.asset.equals(assetPubkey));
+        assert(offer.bidder.equals(bidder.publicKey));
+        assert(offer.lamports.eq(offerLamports));
+        const vaultLamports = (await provider.connection.getAccountInfo(vaultPDA))!.lamports;
+        assert(vaultLamports == vaultInitialLamports + offerLamports.toNumber());
+
+        // cancel offer, lamports and rent go back to the bidder
+        const bidderInitialLamports = (await provider.connection.getAccountInfo(bidder.publicKey))!.lamports;
+        await program.methods
+            .cancelOffer()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                bidder: bidder.publicKey,
+                offer: offerPDA(assetPubkey, bidder.publicKey),
+            })
+            .signers([bidder])
+            .rpc();
+
+        const offerAccountInfo = await provider.connection.getAccountInfo(offerPDA(assetPubkey, bidder.publicKey));
+        assert(offerAccountInfo == null);
+        const bidderLamports = (await provider.connection.getAccountInfo(bidder.publicKey))!.lamports;
+        assert(bidderLamports > bidderInitialLamports + offerLamports.toNumber());
+    });
+
+    it("accept offer", async () => {
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        const bidder = await createSigner();
+        const offerLamports = new BN(2 * LAMPORTS_PER_SOL);
+        await makeOffer(assetPubkey, bidder, offerLamports);
+
+        // owner accepts, asset goes to the bidder and the offer is settled like an auction
+        const tresuaryInitialLamports = (await provider.connection.getAccountInfo(tresuaryPDA))?.lamports ?? 0;
+        await program.methods
+            .acceptOffer()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                bidder: bidder.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == bidder.publicKey.toBase58());
+
+        const offerAccountInfo = await provider.connection.getAccountInfo(offerPDA(assetPubkey, bidder.publicKey));
+        assert(offerAccountInfo == null);
+        const tresuaryLamports = (await provider.connection.getAccountInfo(tresuaryPDA))!.lamports;
+        assert(tresuaryLamports == tresuaryInitialLamports + offerLamports.toNumber() / 100);
+    });
+
+    it("owner can not make an offer", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        let failed = false;
+        try {
+            await makeOffer(assetPubkey, payerWallet.payer, new BN(LAMPORTS_PER_SOL));
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "OwnerBid")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("offers on listed assets can not be accepted", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        const bidder = await createSigner();
+        await makeOffer(assetPubkey, bidder, new BN(LAMPORTS_PER_SOL));
+
+        // list asset, it is frozen by the asset auction
+        await program.methods
+            .createAssetAuction(60, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        let failed = false;
+        try {
+            await program.methods
+                .acceptOffer()
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    bidder: bidder.publicKey,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            failed = true
+        } finally {
+            assert(failed)
+        }
+    });
+});
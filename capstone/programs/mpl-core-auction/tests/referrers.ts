@@ -0,0 +1,362 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Referrer fees", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 28,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+
+    // referrer shares of the protocol fee, in bps of the fee
+    const referrerFee = {bidBps: 2000, listingBps: 1000};
+
+    before("set referrer fee", async () => {
+        await program.methods
+            .setReferrerFee(referrerFee)
+            .accountsPartial({config: auctionConfigPDA})
+            .rpc();
+    });
+
+    // helper function to create an english asset auction that ends at the first bid
+    async function createAssetAuction(listingReferrer: anchor.web3.PublicKey | null, buyNow: BN | null = null): Promise<anchor.web3.PublicKey> {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+        await program.methods
+            .createAssetAuction(0, new BN(100), buyNow, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA, listingReferrer})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+        return assetPubkey;
+    }
+
+    // helper function to place the opening bid through a referrer
+    async function bid(assetPubkey: anchor.web3.PublicKey, buyer: anchor.web3.Keypair, lamports: BN, referrer: anchor.web3.PublicKey | null) {
+        await program.methods
+            .bidAssetAuction(lamports, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+                referrer,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+    }
+
+    // helper function to complete an auction with its referrers
+    async function complete(assetPubkey: anchor.web3.PublicKey, buyer: anchor.web3.PublicKey, bidReferrer: anchor.web3.PublicKey | null, listingReferrer: anchor.web3.PublicKey | null) {
+        await program.methods
+            .completeAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                owner: signer.publicKey,
+                bidReferrer,
+                listingReferrer,
+            })
+            .accounts({
+                buyer,
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    // helper function to fetch the lamports of an account
+    async function lamports(pubkey: anchor.web3.PublicKey): Promise<number> {
+        return (await provider.connection.getAccountInfo(pubkey))?.lamports ?? 0;
+    }
+
+    it("referrers get their share of the fee", async () => {
+        const listingReferrer = await createSigner();
+        const bidReferrer = await createSigner();
+        const assetPubkey = await createAssetAuction(listingReferrer.publicKey);
+
+        const buyer = await createSigner();
+        const buyerBid = new BN(2 * LAMPORTS_PER_SOL);
+        await bid(assetPubkey, buyer, buyerBid, bidReferrer.publicKey);
+
+        // referrers are recorded on the auction
+        const [assetAuctionPDA, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const assetAuction = await program.account.assetAuction.fetch(assetAuctionPDA);
+        assert(assetAuction.listingReferrer.equals(listingReferrer.publicKey));
+        assert(assetAuction.bidReferrer.equals(bidReferrer.publicKey));
+
+        const tresuaryStartingLamports = await lamports(tresuaryPDA);
+        await complete(assetPubkey, buyer.publicKey, bidReferrer.publicKey, listingReferrer.publicKey);
+
+        // shares are carved out of the 1% fee
+        const feeLamports = buyerBid.toNumber() * initParams.feeBPS / 10_000;
+        const bidReferrerLamports = feeLamports * referrerFee.bidBps / 10_000;
+        const listingReferrerLamports = feeLamports * referrerFee.listingBps / 10_000;
+        assert(await lamports(bidReferrer.publicKey) == 10 * LAMPORTS_PER_SOL + bidReferrerLamports);
+        assert(await lamports(listingReferrer.publicKey) == 10 * LAMPORTS_PER_SOL + listingReferrerLamports);
+        assert(await lamports(tresuaryPDA) == tresuaryStartingLamports + feeLamports - bidReferrerLamports - listingReferrerLamports);
+    });
+
+    it("sales without referrers keep the whole fee", async () => {
+        const assetPubkey = await createAssetAuction(null);
+        const buyer = await createSigner();
+        const buyerBid = new BN(LAMPORTS_PER_SOL);
+        await bid(assetPubkey, buyer, buyerBid, null);
+
+        const tresuaryStartingLamports = await lamports(tresuaryPDA);
+        await complete(assetPubkey, buyer.publicKey, null, null);
+        assert(await lamports(tresuaryPDA) == tresuaryStartingLamports + buyerBid.toNumber() * initParams.feeBPS / 10_000);
+    });
+
+    it("dust share of an empty referrer goes to the tresuary", async () => {
+        const assetPubkey = await createAssetAuction(null);
+        const buyer = await createSigner();
+
+        // bid share is below the rent exempt minimum of an empty account
+        const bidReferrer = anchor.web3.Keypair.generate();
+        const buyerBid = new BN(LAMPORTS_PER_SOL / 10);
+        await bid(assetPubkey, buyer, buyerBid, bidReferrer.publicKey);
+
+        const tresuaryStartingLamports = await lamports(tresuaryPDA);
+        await complete(assetPubkey, buyer.publicKey, bidReferrer.publicKey, null);
+
+        // the sale settles and the tresuary keeps the whole fee
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+        assert(await lamports(bidReferrer.publicKey) == 0);
+        assert(await lamports(tresuaryPDA) == tresuaryStartingLamports + buyerBid.toNumber() * initParams.feeBPS / 10_000);
+    });
+
+    it("buy now pays the listing referrer", async () => {
+        const listingReferrer = await createSigner();
+        const buyNow = new BN(2 * LAMPORTS_PER_SOL);
+        const assetPubkey = await createAssetAuction(listingReferrer.publicKey, buyNow);
+
+        const buyer = await createSigner();
+        const tresuaryStartingLamports = await lamports(tresuaryPDA);
+        await program.methods
+            .buyNowAssetAuction([])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+                owner: signer.publicKey,
+                listingReferrer: listingReferrer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // listing share is carved out of the 1% fee
+        const feeLamports = buyNow.toNumber() * initParams.feeBPS / 10_000;
+        const listingReferrerLamports = feeLamports * referrerFee.listingBps / 10_000;
+        assert(await lamports(listingReferrer.publicKey) == 10 * LAMPORTS_PER_SOL + listingReferrerLamports);
+        assert(await lamports(tresuaryPDA) == tresuaryStartingLamports + feeLamports - listingReferrerLamports);
+    });
+
+    it("try complete without the recorded referrer raises", async () => {
+        const assetPubkey = await createAssetAuction(null);
+        const buyer = await createSigner();
+        const bidReferrer = await createSigner();
+        await bid(assetPubkey, buyer, new BN(LAMPORTS_PER_SOL), bidReferrer.publicKey);
+
+        let failed = false;
+        try {
+            await complete(assetPubkey, buyer.publicKey, null, null);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidReferrer")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try self referral raises", async () => {
+        const assetPubkey = await createAssetAuction(null);
+        const buyer = await createSigner();
+
+        let failed = false;
+        try {
+            await bid(assetPubkey, buyer, new BN(LAMPORTS_PER_SOL), buyer.publicKey);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidReferrer")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try invalid referrer fee raises", async () => {
+        let failed = false;
+        try {
+            await program.methods
+                .setReferrerFee({bidBps: 6000, listingBps: 5000})
+                .accountsPartial({config: auctionConfigPDA})
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidReferrerFee")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
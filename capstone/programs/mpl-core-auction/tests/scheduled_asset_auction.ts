@@ -0,0 +1,342 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Scheduled asset auction", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 15,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to get the cluster unix timestamp
+    async function getUnixTimestamp(): Promise<number> {
+        const slot = await provider.connection.getSlot();
+        return (await provider.connection.getBlockTime(slot))!;
+    }
+
+    // helper function to wait for the cluster clock
+    async function sleep(seconds: number) {
+        await new Promise((resolve) => setTimeout(resolve, seconds * 1000));
+    }
+
+    // helper function to create a scheduled asset auction
+    async function createScheduledAssetAuction(assetPubkey: anchor.web3.PublicKey, startTs: number, endTs: number) {
+        await program.methods
+            .createAssetAuction(0, new BN(100), null, null, {startTs: new BN(startTs), endTs: new BN(endTs)})
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    // helper function to bid on an asset auction
+    async function bidAssetAuction(assetPubkey: anchor.web3.PublicKey, buyer: anchor.web3.Keypair, lamports: BN) {
+        await program.methods
+            .bidAssetAuction(lamports, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([buyer])
+            .rpc();
+    }
+
+    // helper function to expire an asset auction
+    async function expireAssetAuction(assetPubkey: anchor.web3.PublicKey) {
+        await program.methods
+            .expireAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    it("create scheduled asset auction", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        const now = await getUnixTimestamp();
+        const startTs = now - 60;
+        const endTs = now + 3600;
+        await createScheduledAssetAuction(assetPubkey, startTs, endTs);
+
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const assetAuctionAccount = await program.account.assetAuction.fetch(assetAuctionPDA);
+        assert(assetAuctionAccount.startTimestamp.eq(new BN(startTs)));
+        assert(assetAuctionAccount.endTimestamp.eq(new BN(endTs)));
+        assert(assetAuctionAccount.durationMinutes === (endTs - startTs) / 60);
+
+        // first bid keeps the scheduled end
+        const buyer = await createSigner();
+        await bidAssetAuction(assetPubkey, buyer, new BN(anchor.web3.LAMPORTS_PER_SOL));
+        const biddedAssetAuctionAccount = await program.account.assetAuction.fetch(assetAuctionPDA);
+        assert(biddedAssetAuctionAccount.endTimestamp.eq(new BN(endTs)));
+    });
+
+    it("try bid before start raises", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        const now = await getUnixTimestamp();
+        await createScheduledAssetAuction(assetPubkey, now + 3600, now + 7200);
+
+        const buyer = await createSigner();
+        let failed = false;
+        try {
+            await bidAssetAuction(assetPubkey, buyer, new BN(anchor.web3.LAMPORTS_PER_SOL));
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "AuctionNotOpen")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try create scheduled asset auction ending in the past raises", async () => {
+        const asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        const now = await getUnixTimestamp();
+        let failed = false;
+        try {
+            await createScheduledAssetAuction(assetPubkey, now - 7200, now - 3600);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidSchedule")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("expire scheduled asset auction without bids", async () => {
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        const now = await getUnixTimestamp();
+        await createScheduledAssetAuction(assetPubkey, now - 60, now + 2);
+
+        // should fail because auction is running
+        let failed = false;
+        try {
+            await expireAssetAuction(assetPubkey);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "AuctionRunning")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        // anyone can expire once the end is reached, the owner does not sign
+        await sleep(4);
+        await expireAssetAuction(assetPubkey);
+
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == signer.publicKey.toString());
+        assert(asset.freezeDelegate?.frozen == false);
+        assert(asset.freezeDelegate?.authority.type == "Owner");
+        assert(asset.transferDelegate?.authority.type == "Owner");
+    });
+
+    it("scheduled asset auction with bid is completed", async () => {
+        let asset = await createAsset();
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        const now = await getUnixTimestamp();
+        await createScheduledAssetAuction(assetPubkey, now - 60, now + 2);
+
+        const buyer = await createSigner();
+        await bidAssetAuction(assetPubkey, buyer, new BN(anchor.web3.LAMPORTS_PER_SOL));
+        await sleep(4);
+
+        // should fail because the auction has a bid to settle
+        let failed = false;
+        try {
+            await expireAssetAuction(assetPubkey);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "AuctionStarted")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        await program.methods
+            .completeAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                owner: signer.publicKey,
+            })
+            .accounts({
+                buyer: buyer.publicKey,
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+    });
+});
@@ -0,0 +1,365 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { createHash, randomBytes } from "crypto";
+
+
+describe("Sealed asset auction", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 9,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to commit to a sealed bid
+    function sealedBidCommitment(lamports: BN, salt: Buffer): number[] {
+        const preimage = Buffer.concat([lamports.toArrayLike(Buffer, "le", 8), salt]);
+        return Array.from(createHash("sha256").update(preimage).digest());
+    }
+
+    // helper function to list an asset in a sealed auction
+    async function createSealedAssetAuction(commitMinutes: number, minBid: BN): Promise<anchor.web3.PublicKey> {
+        // create asset 
+        const asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create sealed asset auction
+        await program.methods
+            .createSealedAssetAuction(commitMinutes, 60, minBid, 500)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        return assetPubkey
+    }
+
+    it("create sealed asset auction", async () => {
+        // create sealed asset auction
+        const minBid = new BN(anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createSealedAssetAuction(60, minBid)
+
+        // get asset auction PDA
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+
+        // fetch asset auction account
+        const assetAuctionAccount = await program.account.assetAuction.fetch(assetAuctionPDA);
+        const sealed = assetAuctionAccount.kind.sealed
+        assert(sealed != undefined, "Auction is not sealed");
+        assert(sealed.unrevealedPenaltyBps === 500);
+        assert(sealed.openBids === 0);
+        assert(!sealed.settled);
+        assert(assetAuctionAccount.minBidLamports.eq(minBid));
+        assert(assetAuctionAccount.endTimestamp.eq(sealed.commitEndTimestamp.add(new BN(60 * 60))));
+
+        // verify custody
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.freezeDelegate?.frozen, "Asset is not frozen");
+    });
+
+    it("commit sealed bid", async () => {
+        // create sealed asset auction
+        const minBid = new BN(anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createSealedAssetAuction(60, minBid)
+
+        // fetch vault starting lamports
+        const vaultStartingLamports = (await program.provider.connection.getAccountInfo(vaultPDA))?.lamports ?? 0
+
+        // commit bid with a deposit higher than the bid
+        const bidder = await createSigner()
+        const salt = randomBytes(32)
+        const bid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        const deposit = new BN(3 * anchor.web3.LAMPORTS_PER_SOL)
+        await program.methods
+            .commitSealedBid(sealedBidCommitment(bid, salt), deposit)
+            .accountsPartial({
+                config: auctionConfigPDA,
+                bidder: bidder.publicKey,
+                payer: bidder.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([bidder])
+            .rpc();
+
+        // get PDAs
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const [sealedBidPDA, bump] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("sealed_bid"), assetAuctionPDA.toBuffer(), bidder.publicKey.toBuffer()],
+            program.programId,
+        );
+
+        // verify sealed bid
+        const sealedBidAccount = await program.account.sealedBid.fetch(sealedBidPDA);
+        assert(sealedBidAccount.bidder.toBase58() === bidder.publicKey.toBase58());
+        assert(sealedBidAccount.funder.toBase58() === bidder.publicKey.toBase58());
+        assert(sealedBidAccount.depositLamports.eq(deposit));
+        assert(!sealedBidAccount.revealed);
+        assert(sealedBidAccount.bump === bump);
+
+        // verify auction started and deposit is in the vault
+        const assetAuctionAccount = await program.account.assetAuction.fetch(assetAuctionPDA);
+        assert(assetAuctionAccount.kind.sealed?.openBids === 1);
+        assert(assetAuctionAccount.firstBidTimestamp.gt(new BN(0)));
+        const vaultAccountInfo = await program.provider.connection.getAccountInfo(vaultPDA)
+        assert(vaultAccountInfo?.lamports == vaultStartingLamports + deposit.toNumber())
+    });
+
+    it("try reveal during commit phase raises", async () => {
+        // create sealed asset auction
+        const minBid = new BN(anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createSealedAssetAuction(60, minBid)
+
+        // commit bid
+        const bidder = await createSigner()
+        const salt = randomBytes(32)
+        const bid = new BN(2 * anchor.web3.LAMPORTS_PER_SOL)
+        await program.methods
+            .commitSealedBid(sealedBidCommitment(bid, salt), bid)
+            .accountsPartial({
+                config: auctionConfigPDA,
+                bidder: bidder.publicKey,
+                payer: bidder.publicKey,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .signers([bidder])
+            .rpc();
+
+        // get PDAs
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const [sealedBidPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("sealed_bid"), assetAuctionPDA.toBuffer(), bidder.publicKey.toBuffer()],
+            program.programId,
+        );
+
+        // should fail because commit phase is running
+        let failed = false;
+        try {
+            await program.methods
+                .revealSealedBid(bid, Array.from(salt))
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    sealedBid: sealedBidPDA,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "RevealPhaseNotStarted")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        // should fail because auction is not settled
+        failed = false;
+        try {
+            await program.methods
+                .refundSealedBid()
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    bidder: bidder.publicKey,
+                    funder: bidder.publicKey,
+                    owner: signer.publicKey,
+                    collectionAuction: auctionCollectionPDA,
+                    assetAuction: assetAuctionPDA,
+                    sealedBid: sealedBidPDA,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "AuctionNotSettled")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try commit after commit phase raises", async () => {
+        // create sealed asset auction with an empty commit phase
+        const minBid = new BN(anchor.web3.LAMPORTS_PER_SOL)
+        const assetPubkey = await createSealedAssetAuction(0, minBid)
+
+        // should fail because commit phase is over
+        const bidder = await createSigner()
+        let failed = false;
+        try {
+            await program.methods
+                .commitSealedBid(sealedBidCommitment(minBid, randomBytes(32)), minBid)
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    bidder: bidder.publicKey,
+                    payer: bidder.publicKey,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .signers([bidder])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "CommitPhaseOver")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
@@ -0,0 +1,475 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+    TOKEN_PROGRAM_ID,
+    createMint,
+    getAccount,
+    getAssociatedTokenAddressSync,
+    getOrCreateAssociatedTokenAccount,
+    mintTo,
+} from "@solana/spl-token";
+
+
+describe("Asset auction in quote mint", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 11,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    // quote mint and token accounts
+    const tokenAirdrop = 10_000;
+    let quoteMint: anchor.web3.PublicKey;
+    let vaultTokenAccount: anchor.web3.PublicKey;
+    let tresuaryTokenAccount: anchor.web3.PublicKey;
+    let ownerTokenAccount: anchor.web3.PublicKey;
+
+    // helper function to create keypair with quote mint funds
+    async function createTokenBuyer(): Promise<[anchor.web3.Keypair, anchor.web3.PublicKey]> {
+        const buyer = await createSigner();
+        const buyerTokenAccount = await getOrCreateAssociatedTokenAccount(
+            provider.connection, payerWallet.payer, quoteMint, buyer.publicKey,
+        );
+        await mintTo(
+            provider.connection, payerWallet.payer, quoteMint, buyerTokenAccount.address, payerWallet.payer, tokenAirdrop,
+        );
+
+        return [buyer, buyerTokenAccount.address]
+    }
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create quote mint", async () => {
+        quoteMint = await createMint(provider.connection, payerWallet.payer, payerWallet.publicKey, null, 6);
+        vaultTokenAccount = getAssociatedTokenAddressSync(quoteMint, vaultPDA, true);
+        tresuaryTokenAccount = getAssociatedTokenAddressSync(quoteMint, tresuaryPDA, true);
+        ownerTokenAccount = getAssociatedTokenAddressSync(quoteMint, payerWallet.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection with quote mint
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({
+                config: auctionConfigPDA,
+                vaultTokenAccount: vaultTokenAccount,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                quoteMint: quoteMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .rpc();
+    });
+
+    it("create collection auction with quote mint", async () => {
+        const collectionAuctionAccount = await program.account.collectionAuction.fetch(auctionCollectionPDA);
+        assert(collectionAuctionAccount.quoteMint?.toBase58() === quoteMint.toBase58());
+    });
+
+    it("bid in quote mint", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction
+        await program.methods
+            .createAssetAuction(1, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // create first bid
+        const [buyer, buyerTokenAccount] = await createTokenBuyer();
+        const buyerBid = new BN(200);
+        await program.methods
+            .bidAssetAuction(buyerBid, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                payerTokenAccount: buyerTokenAccount,
+                vaultTokenAccount: vaultTokenAccount,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+                quoteMint: quoteMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // create second bid, first buyer bid is credited to its refund account
+        const [buyer2, buyer2TokenAccount] = await createTokenBuyer();
+        const buyer2Bid = new BN(400);
+        await program.methods
+            .bidAssetAuction(buyer2Bid, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer2.publicKey,
+                payer: buyer2.publicKey,
+                payerTokenAccount: buyer2TokenAccount,
+                vaultTokenAccount: vaultTokenAccount,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+                quoteMint: quoteMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([buyer2])
+            .rpc();
+
+        // first buyer withdraws its refund in quote mint
+        const [assetAuctionPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+            [auctionCollectionPDA.toBuffer(), assetPubkey.toBuffer()],
+            program.programId,
+        );
+        const [bidRefundPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("refund"), assetAuctionPDA.toBuffer(), buyer.publicKey.toBuffer(), buyer.publicKey.toBuffer()],
+            program.programId,
+        );
+        const bidRefundAccount = await program.account.bidRefund.fetch(bidRefundPDA);
+        assert(bidRefundAccount.quoteMint?.toBase58() === quoteMint.toBase58());
+        assert(bidRefundAccount.lamports.eq(buyerBid));
+        await program.methods
+            .withdrawRefund()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                authority: buyer.publicKey,
+                funder: buyer.publicKey,
+                rentPayer: buyer2.publicKey,
+                bidRefund: bidRefundPDA,
+                funderTokenAccount: buyerTokenAccount,
+                vaultTokenAccount: vaultTokenAccount,
+            })
+            .accounts({
+                quoteMint: quoteMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // check token balances
+        const buyerAccount = await getAccount(provider.connection, buyerTokenAccount);
+        const buyer2Account = await getAccount(provider.connection, buyer2TokenAccount);
+        const vaultAccount = await getAccount(provider.connection, vaultTokenAccount);
+        assert(buyerAccount.amount == BigInt(tokenAirdrop));
+        assert(buyer2Account.amount == BigInt(tokenAirdrop - buyer2Bid.toNumber()));
+        assert(vaultAccount.amount == BigInt(buyer2Bid.toNumber()));
+
+        // vault lamports are untouched
+        const vaultAccountInfo = await program.provider.connection.getAccountInfo(vaultPDA);
+        assert(vaultAccountInfo == null);
+    });
+
+    it("complete auction in quote mint", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // create asset auction
+        await program.methods
+            .createAssetAuction(0, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // create first bid
+        const [buyer, buyerTokenAccount] = await createTokenBuyer();
+        const buyerBid = new BN(1000);
+        await program.methods
+            .bidAssetAuction(buyerBid, [])
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                buyer: buyer.publicKey,
+                payer: buyer.publicKey,
+                payerTokenAccount: buyerTokenAccount,
+                vaultTokenAccount: vaultTokenAccount,
+                previousBidRefund: null,
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+                quoteMint: quoteMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([buyer])
+            .rpc();
+
+        // fetch owner and vault token balances before complete
+        const ownerInitialAccount = await getAccount(provider.connection, ownerTokenAccount);
+        const vaultInitialAccount = await getAccount(provider.connection, vaultTokenAccount);
+
+        // complete auction
+        await program.methods
+            .completeAssetAuction()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                collectionAuction: auctionCollectionPDA,
+                owner: signer.publicKey,
+                ownerTokenAccount: ownerTokenAccount,
+                tresuaryTokenAccount: tresuaryTokenAccount,
+                vaultTokenAccount: vaultTokenAccount,
+            })
+            .accounts({
+                buyer: buyer.publicKey,
+                collection: collectionPubkey,
+                asset: assetPubkey,
+                quoteMint: quoteMint,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .rpc();
+
+        // check asset
+        asset = await fetchAsset(umi, asset.publicKey);
+        assert(asset.owner.toString() == buyer.publicKey.toBase58());
+
+        // check fee and proceeds are paid in quote mint
+        const tresuaryExpecteBalance = Math.ceil(buyerBid.toNumber() * 0.01);
+        const ownerExpectedBalanceExtra = Math.floor(buyerBid.toNumber() * 0.99);
+        const tresuaryAccount = await getAccount(provider.connection, tresuaryTokenAccount);
+        const ownerAccount = await getAccount(provider.connection, ownerTokenAccount);
+        const vaultAccount = await getAccount(provider.connection, vaultTokenAccount);
+        assert(tresuaryAccount.amount == BigInt(tresuaryExpecteBalance));
+        assert(ownerAccount.amount == ownerInitialAccount.amount + BigInt(ownerExpectedBalanceExtra));
+        assert(vaultAccount.amount == vaultInitialAccount.amount - BigInt(buyerBid.toNumber()));
+    });
+
+    it("try create dutch asset auction on quote mint collection raises", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // should fail because dutch auctions are paid in lamports
+        let failed = false;
+        try {
+            await program.methods
+                .createDutchAssetAuction(
+                    60,
+                    new BN(2 * anchor.web3.LAMPORTS_PER_SOL),
+                    new BN(anchor.web3.LAMPORTS_PER_SOL),
+                    {linear: {}},
+                )
+                .accountsPartial({config: auctionConfigPDA})
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "QuoteMintNotSupported")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("try referrers on quote mint collection raises", async () => {
+        // create asset 
+        let asset = await createAsset()
+        const assetPubkey = new anchor.web3.PublicKey(asset.publicKey.toString());
+
+        // should fail because referrer shares are paid in lamports
+        const referrer = await createSigner();
+        let failed = false;
+        try {
+            await program.methods
+                .createAssetAuction(1, new BN(100), null, null, null)
+                .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA, listingReferrer: referrer.publicKey})
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                })
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "QuoteMintNotSupported")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+
+        // create asset auction without a listing referrer
+        await program.methods
+            .createAssetAuction(1, new BN(100), null, null, null)
+            .accountsPartial({config: auctionConfigPDA, collectionAuction: auctionCollectionPDA})
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+
+        // should fail because referrer shares are paid in lamports
+        const [buyer, buyerTokenAccount] = await createTokenBuyer();
+        failed = false;
+        try {
+            await program.methods
+                .bidAssetAuction(new BN(200), [])
+                .accountsPartial({
+                    config: auctionConfigPDA,
+                    collectionAuction: auctionCollectionPDA,
+                    buyer: buyer.publicKey,
+                    payer: buyer.publicKey,
+                    payerTokenAccount: buyerTokenAccount,
+                    vaultTokenAccount: vaultTokenAccount,
+                    previousBidRefund: null,
+                    referrer: referrer.publicKey,
+                })
+                .accounts({
+                    collection: collectionPubkey,
+                    asset: assetPubkey,
+                    quoteMint: quoteMint,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                })
+                .signers([buyer])
+                .rpc();
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "QuoteMintNotSupported")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});
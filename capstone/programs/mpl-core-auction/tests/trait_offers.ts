@@ -0,0 +1,300 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { createUmi } from '@metaplex-foundation/umi-bundle-defaults'
+import { 
+    mplCore,
+    createCollection,
+    fetchCollection,
+    create,
+    fetchAsset,
+    AssetV1,
+} from '@metaplex-foundation/mpl-core'
+import { 
+    createSignerFromKeypair,
+    signerIdentity,
+    generateSigner,
+} from '@metaplex-foundation/umi'
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import assert from "assert";
+
+import { MplCoreAuction } from "../target/types/mpl_core_auction";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+
+describe("Trait offers", () => {
+    // configure provider
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);    
+
+    // configure program
+    const program = anchor.workspace.MplCoreAuction as Program<MplCoreAuction>;
+
+    // create a UMI connection to run mpl core commands
+    const umi = createUmi(provider.connection);
+    const payerWallet = provider.wallet as NodeWallet;
+    const keypair = umi.eddsa.createKeypairFromSecretKey(payerWallet.payer.secretKey);
+    const signer = createSignerFromKeypair(umi, keypair);
+    umi.use(signerIdentity(signer));
+    umi.use(mplCore())
+
+    // config params
+    const initParams = {
+        seed: 23,
+        feeBPS: 100,
+        minDurationMinutes: 0,
+        maxDurationMinutes: 14400,
+        extension: {windowMinutes: 0, extensionMinutes: 0, maxExtensions: 0},
+        minBidIncrement: {lamports: {amount: new BN(0)}},
+    };
+
+    // config account pda
+    const [auctionConfigPDA, _] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), new BN(initParams.seed).toArrayLike(Buffer, "le", 4)],
+        program.programId,
+    );
+
+    // vault pda
+    const [vaultPDA, _2] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // tresuary pda
+    const [tresuaryPDA, _3] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tresuary"), auctionConfigPDA.toBuffer()],
+        program.programId,
+    );
+
+    // collection params
+    const collectionSigner = generateSigner(umi)
+    const collectionPubkey = new anchor.web3.PublicKey(collectionSigner.publicKey.toString());
+    const collectionArgs = {
+        collection: collectionSigner,
+        name: 'My Collection',
+        uri: "",
+    };
+
+    // collection auction account pda
+    const [auctionCollectionPDA, _4] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collection"), auctionConfigPDA.toBuffer(), collectionPubkey.toBuffer()],
+        program.programId,
+    );
+
+    // helper function to create asset
+    async function createAsset(): Promise<AssetV1> {
+        // Create asset args
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const assetArgs = {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+        };
+
+        // Create asset
+        await create(umi, assetArgs).sendAndConfirm(umi);
+        return fetchAsset(umi, assetSigner.publicKey)
+    }
+
+    // helper function to create keypair with funds
+    async function createSigner(): Promise<anchor.web3.Keypair> {
+        const signer = anchor.web3.Keypair.generate()
+
+        // airdrop
+        const airdropSignature = await provider.connection.requestAirdrop(
+            signer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL,
+        )
+        const latestBlockHash = await provider.connection.getLatestBlockhash();
+        await provider.connection.confirmTransaction({
+            blockhash: latestBlockHash.blockhash,
+            lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+            signature: airdropSignature,
+        });
+
+        return signer
+    }       
+
+    before("intialize auction", async () => {
+        await program.methods
+            .initialize(
+                initParams.seed,
+                initParams.feeBPS,
+                initParams.minDurationMinutes,
+                initParams.maxDurationMinutes,
+                initParams.extension,
+                initParams.minBidIncrement,
+            )
+            .rpc(); 
+    });
+
+    before("create collection", async () => {
+        await createCollection(umi, collectionArgs).sendAndConfirm(umi);
+
+        // verify collection exists
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        assert(collection.publicKey == collectionSigner.publicKey);
+    });
+
+    before("create collection auction", async () => {
+        // whitelist collection
+        await program.methods
+            .createCollectionAuction(null)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({collection: collectionPubkey})
+            .rpc();
+    });
+
+    // helper function to create an asset with an attributes plugin
+    async function createAssetWithAttributes(attributeList: {key: string, value: string}[], ownerManaged: boolean = false): Promise<anchor.web3.PublicKey> {
+        const assetSigner = generateSigner(umi)
+        const collection = await fetchCollection(umi, collectionSigner.publicKey);
+        const authority = ownerManaged ? {type: 'Owner' as const} : {type: 'UpdateAuthority' as const};
+        await create(umi, {
+            collection: collection,
+            asset: assetSigner,
+            name: 'My asset',
+            uri: "",
+            plugins: [{type: 'Attributes', attributeList, authority}],
+        }).sendAndConfirm(umi);
+        return new anchor.web3.PublicKey(assetSigner.publicKey.toString());
+    }
+
+    // helper function to derive a trait offer pda
+    function traitOfferPDA(bidder: anchor.web3.PublicKey, seed: number): anchor.web3.PublicKey {
+        const [traitOffer, _5] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("trait_offer"), auctionCollectionPDA.toBuffer(), bidder.toBuffer(), new BN(seed).toArrayLike(Buffer, "le", 4)],
+            program.programId,
+        );
+        return traitOffer;
+    }
+
+    // helper function to make a trait offer expiring in an hour
+    async function makeTraitOffer(bidder: anchor.web3.Keypair, seed: number, lamports: BN, traits: {key: string, value: string}[]) {
+        const expiryTimestamp = new BN(Math.floor(Date.now() / 1000) + 3600);
+        await program.methods
+            .makeTraitOffer(seed, lamports, traits, expiryTimestamp)
+            .accountsPartial({config: auctionConfigPDA})
+            .accounts({
+                bidder: bidder.publicKey,
+                collection: collectionPubkey,
+            })
+            .signers([bidder])
+            .rpc();
+    }
+
+    // helper function to accept a trait offer with an asset of the payer wallet
+    async function acceptTraitOffer(bidder: anchor.web3.PublicKey, seed: number, assetPubkey: anchor.web3.PublicKey) {
+        await program.methods
+            .acceptTraitOffer()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                bidder: bidder,
+                traitOffer: traitOfferPDA(bidder, seed),
+            })
+            .accounts({
+                collection: collectionPubkey,
+                asset: assetPubkey,
+            })
+            .rpc();
+    }
+
+    it("accept trait offer with matching attributes", async () => {
+        const bidder = await createSigner();
+        const traits = [{key: "rarity", value: "legendary"}, {key: "class", value: "mage"}];
+        await makeTraitOffer(bidder, 0, new BN(LAMPORTS_PER_SOL), traits);
+
+        const traitOffer = await program.account.traitOffer.fetch(traitOfferPDA(bidder.publicKey, 0));
+        assert(traitOffer.collectionAuction.equals(auctionCollectionPDA));
+        assert(traitOffer.traits.length == 2);
+        assert(traitOffer.traits[0].key == "rarity" && traitOffer.traits[0].value == "legendary");
+
+        // asset has the traits and more
+        const assetPubkey = await createAssetWithAttributes([
+            {key: "class", value: "mage"},
+            {key: "level", value: "12"},
+            {key: "rarity", value: "legendary"},
+        ]);
+        await acceptTraitOffer(bidder.publicKey, 0, assetPubkey);
+
+        const asset = await fetchAsset(umi, assetPubkey.toString());
+        assert(asset.owner.toString() == bidder.publicKey.toBase58());
+        const traitOfferAccountInfo = await provider.connection.getAccountInfo(traitOfferPDA(bidder.publicKey, 0));
+        assert(traitOfferAccountInfo == null);
+    });
+
+    it("attributes do not match", async () => {
+        const bidder = await createSigner();
+        await makeTraitOffer(bidder, 1, new BN(LAMPORTS_PER_SOL), [{key: "rarity", value: "legendary"}]);
+
+        // wrong value and missing plugin
+        const commonAssetPubkey = await createAssetWithAttributes([{key: "rarity", value: "common"}]);
+        const plainAsset = await createAsset();
+        for (const assetPubkey of [commonAssetPubkey, new anchor.web3.PublicKey(plainAsset.publicKey.toString())]) {
+            let failed = false;
+            try {
+                await acceptTraitOffer(bidder.publicKey, 1, assetPubkey);
+            } catch (error) {
+                if (error instanceof AnchorError) {
+                    assert(error.error.errorCode.code === "TraitMismatch")
+                    failed = true
+                }
+            } finally {
+                assert(failed)
+            }
+        }
+
+        // cancel trait offer, lamports and rent go back to the bidder
+        const bidderInitialLamports = (await provider.connection.getAccountInfo(bidder.publicKey))!.lamports;
+        await program.methods
+            .cancelTraitOffer()
+            .accountsPartial({
+                config: auctionConfigPDA,
+                bidder: bidder.publicKey,
+                traitOffer: traitOfferPDA(bidder.publicKey, 1),
+            })
+            .signers([bidder])
+            .rpc();
+
+        const traitOfferAccountInfo = await provider.connection.getAccountInfo(traitOfferPDA(bidder.publicKey, 1));
+        assert(traitOfferAccountInfo == null);
+        const bidderLamports = (await provider.connection.getAccountInfo(bidder.publicKey))!.lamports;
+        assert(bidderLamports > bidderInitialLamports + LAMPORTS_PER_SOL);
+    });
+
+    it("try accept with owner managed attributes raises", async () => {
+        const bidder = await createSigner();
+        await makeTraitOffer(bidder, 3, new BN(LAMPORTS_PER_SOL), [{key: "rarity", value: "legendary"}]);
+
+        // the owner could rewrite these attributes to match any offer
+        const assetPubkey = await createAssetWithAttributes([{key: "rarity", value: "legendary"}], true);
+
+        let failed = false;
+        try {
+            await acceptTraitOffer(bidder.publicKey, 3, assetPubkey);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "AttributesNotUpdateAuthority")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+
+    it("invalid traits", async () => {
+        const bidder = await createSigner();
+
+        let failed = false;
+        try {
+            await makeTraitOffer(bidder, 2, new BN(LAMPORTS_PER_SOL), []);
+        } catch (error) {
+            if (error instanceof AnchorError) {
+                assert(error.error.errorCode.code === "InvalidTraits")
+                failed = true
+            }
+        } finally {
+            assert(failed)
+        }
+    });
+});